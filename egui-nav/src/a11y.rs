@@ -0,0 +1,68 @@
+/// The role of a node that contains a route
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Role {
+    /// The body of a nav
+    Region,
+    /// The contents of a sheet or drawer, which block what is behind them
+    Dialog,
+}
+
+/// Render `contents` as the children of an AccessKit node. Without the
+/// `accesskit` feature this only runs `contents`.
+pub(crate) fn node<R>(
+    ctx: &egui::Context,
+    id: egui::Id,
+    role: Role,
+    label: Option<&str>,
+    contents: impl FnOnce() -> R,
+) -> R {
+    #[cfg(feature = "accesskit")]
+    ctx.accesskit_node_builder(id, |node| {
+        use egui::accesskit;
+
+        match role {
+            Role::Region => node.set_role(accesskit::Role::Region),
+            Role::Dialog => {
+                node.set_role(accesskit::Role::Dialog);
+                node.set_modal();
+            }
+        }
+        if let Some(label) = label {
+            node.set_label(label);
+        }
+    });
+    #[cfg(not(feature = "accesskit"))]
+    let _ = (role, label);
+
+    ctx.with_accessibility_parent(id, contents)
+}
+
+/// Have screen readers read out `text` through the live region at `id`
+pub(crate) fn announce(ctx: &egui::Context, id: egui::Id, text: String) {
+    #[cfg(feature = "accesskit")]
+    ctx.data_mut(|d| d.insert_temp(id, text));
+    #[cfg(not(feature = "accesskit"))]
+    let _ = (ctx, id, text);
+}
+
+/// A polite live region holding the last announcement. Screen readers
+/// speak its label whenever it changes, so it has to be emitted every
+/// frame.
+pub(crate) fn live_region(ctx: &egui::Context, id: egui::Id) {
+    #[cfg(feature = "accesskit")]
+    {
+        let Some(text) = ctx.data(|d| d.get_temp::<String>(id)) else {
+            return;
+        };
+
+        ctx.accesskit_node_builder(id, |node| {
+            use egui::accesskit;
+
+            node.set_role(accesskit::Role::Status);
+            node.set_live(accesskit::Live::Polite);
+            node.set_label(text);
+        });
+    }
+    #[cfg(not(feature = "accesskit"))]
+    let _ = (ctx, id);
+}
@@ -0,0 +1,177 @@
+/// A time based animation. Transitions take `duration` seconds regardless
+/// of the frame rate, and `easing` maps the linear time (0.0 to 1.0) to
+/// the animation's progress.
+#[derive(Clone, Copy, Debug)]
+pub struct Animation {
+    pub duration: f32,
+    pub easing: fn(f32) -> f32,
+}
+
+impl Animation {
+    pub fn new(duration: f32) -> Self {
+        Self {
+            duration,
+            easing: ease_out_cubic,
+        }
+    }
+
+    pub fn easing(mut self, easing: fn(f32) -> f32) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self::new(0.25)
+    }
+}
+
+pub fn linear(t: f32) -> f32 {
+    t
+}
+
+pub fn ease_out_cubic(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+pub fn ease_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
+
+/// Don't let a fling shorten an animation below this many seconds
+const MIN_FLING_DURATION: f32 = 0.08;
+
+/// An in-flight animation of the offset
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct Tween {
+    from: f32,
+    to: f32,
+    /// the value we left the offset at last frame
+    value: f32,
+    elapsed: f32,
+    duration: f32,
+}
+
+impl Tween {
+    /// Start animating from `from` to `to`. If we're already moving
+    /// towards `to` at `velocity` points per second, the animation is
+    /// shortened so that it starts at that speed.
+    pub(crate) fn new(from: f32, to: f32, velocity: f32, animation: &Animation) -> Self {
+        let mut duration = animation.duration;
+
+        let distance = to - from;
+        let towards_target = velocity * distance > 0.0;
+        // the initial slope of the easing curve
+        let slope = (animation.easing)(0.001) / 0.001;
+        if towards_target && slope > 0.01 {
+            let fling_duration = slope * distance.abs() / velocity.abs();
+            duration = fling_duration.clamp(MIN_FLING_DURATION.min(duration), duration);
+        }
+
+        Tween {
+            from,
+            to,
+            value: from,
+            elapsed: 0.0,
+            duration,
+        }
+    }
+
+    /// Is this animating from `offset` to `target`, and has nobody moved
+    /// the offset or changed the target on us?
+    pub(crate) fn continues(&self, offset: f32, target: f32) -> bool {
+        self.to == target && self.value == offset
+    }
+
+    /// Advance the animation by `dt` seconds. Returns the new value, or
+    /// None once we've reached the target.
+    pub(crate) fn step(&mut self, dt: f32, animation: &Animation) -> Option<f32> {
+        self.elapsed += dt;
+        let t = if self.duration > 0.0 {
+            (self.elapsed / self.duration).min(1.0)
+        } else {
+            1.0
+        };
+
+        if t >= 1.0 {
+            return None;
+        }
+
+        self.value = egui::lerp(self.from..=self.to, (animation.easing)(t));
+        Some(self.value)
+    }
+}
+
+/// A damped spring. Unlike a timed [`Animation`], a spring keeps the
+/// velocity of a released drag or an interrupted transition, so motion
+/// never restarts from zero speed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spring {
+    pub stiffness: f32,
+    pub damping: f32,
+    pub mass: f32,
+}
+
+/// Distance and speed below which a spring counts as settled
+const SPRING_REST_DISTANCE: f32 = 0.5;
+const SPRING_REST_VELOCITY: f32 = 10.0;
+
+/// Step the simulation in small increments so stiff springs stay stable
+/// at low frame rates
+const SPRING_MAX_STEP: f32 = 1.0 / 240.0;
+
+impl Spring {
+    pub fn new(stiffness: f32, damping: f32, mass: f32) -> Self {
+        Self {
+            stiffness,
+            damping,
+            mass,
+        }
+    }
+
+    /// A spring that settles as fast as possible without overshooting
+    pub fn critically_damped(stiffness: f32) -> Self {
+        Self::new(stiffness, 2.0 * stiffness.sqrt(), 1.0)
+    }
+
+    /// Advance `offset` and `velocity` towards `target` by `dt` seconds.
+    /// Returns true once the spring has settled at the target.
+    pub(crate) fn step(&self, offset: &mut f32, velocity: &mut f32, target: f32, dt: f32) -> bool {
+        let mut remaining = dt;
+        while remaining > 0.0 {
+            let dt = remaining.min(SPRING_MAX_STEP);
+            remaining -= dt;
+
+            let force = -self.stiffness * (*offset - target) - self.damping * *velocity;
+            *velocity += force / self.mass * dt;
+            *offset += *velocity * dt;
+        }
+
+        (*offset - target).abs() < SPRING_REST_DISTANCE && velocity.abs() < SPRING_REST_VELOCITY
+    }
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Self::critically_damped(600.0)
+    }
+}
+
+/// How a component moves its offset towards a target
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Motion {
+    Timed(Animation),
+    Spring(Spring),
+}
+
+impl Default for Motion {
+    fn default() -> Self {
+        Motion::Spring(Spring::default())
+    }
+}
@@ -1,5 +1,8 @@
-use crate::util;
-use egui::{Pos2, Sense, Stroke, Vec2};
+use crate::{util, LayoutDirection};
+use egui::{
+    vec2, Align, Layout, Pos2, Rect, Sense, Stroke, TextStyle, TextWrapMode, Vec2, WidgetInfo,
+    WidgetText, WidgetType,
+};
 use std::fmt::Display;
 
 #[derive(Clone, Copy)]
@@ -7,6 +10,7 @@ pub struct DefaultNavTitle {
     stroke: Option<Stroke>,
     chevron_size: Vec2,
     padding: f32,
+    layout_direction: Option<LayoutDirection>,
 }
 
 impl Default for DefaultNavTitle {
@@ -15,6 +19,7 @@ impl Default for DefaultNavTitle {
             stroke: None,
             chevron_size: Vec2::new(14.0, 20.0),
             padding: 4.0,
+            layout_direction: None,
         }
     }
 }
@@ -28,28 +33,31 @@ impl DefaultNavTitle {
         Self::default()
     }
 
+    /// Which way the back chevron points. Detected from the ui's layout
+    /// when not set.
+    pub fn layout_direction(mut self, direction: LayoutDirection) -> Self {
+        self.layout_direction = Some(direction);
+        self
+    }
+
     pub fn ui<R: Display>(&self, ui: &mut egui::Ui, routes: &[R]) -> Option<DefaultTitleResponse> {
         // default route ui
         let mut header_rect = ui.available_rect_before_wrap();
         header_rect.set_height(self.chevron_size.y + 4.0);
 
+        let direction = self
+            .layout_direction
+            .unwrap_or_else(|| LayoutDirection::from_ui(ui));
+        let layout = match direction {
+            LayoutDirection::LeftToRight => Layout::left_to_right(Align::Center),
+            LayoutDirection::RightToLeft => Layout::right_to_left(Align::Center),
+        };
+
         let back = util::arr_top_n(routes, 1);
         let response = back.map(|back| {
             ui.put(header_rect, |ui: &mut egui::Ui| {
-                ui.horizontal_centered(|ui| {
-                    let stroke = self
-                        .stroke
-                        .unwrap_or_else(|| Stroke::new(2.0, ui.visuals().hyperlink_color));
-
-                    let chev_response = chevron(ui, self.padding, self.chevron_size, stroke);
-
-                    let label_response = ui.add(
-                        egui::Label::new(back.to_string())
-                            .sense(Sense::click())
-                            .selectable(false),
-                    );
-
-                    let response = chev_response.union(label_response);
+                ui.with_layout(layout, |ui| {
+                    let response = self.back_button(ui, back.to_string(), direction);
 
                     if let Some(cursor) = ui.visuals().interact_cursor {
                         if response.hovered() {
@@ -73,21 +81,81 @@ impl DefaultNavTitle {
             None
         }
     }
-}
 
-fn chevron(ui: &mut egui::Ui, pad: f32, size: Vec2, stroke: impl Into<Stroke>) -> egui::Response {
-    let (r, painter) = ui.allocate_painter(size, Sense::click());
+    /// The chevron and the name of the previous route, as a single button
+    /// so screen readers see one "Back to ..." control
+    fn back_button(
+        &self,
+        ui: &mut egui::Ui,
+        back: String,
+        direction: LayoutDirection,
+    ) -> egui::Response {
+        let stroke = self
+            .stroke
+            .unwrap_or_else(|| Stroke::new(2.0, ui.visuals().hyperlink_color));
+
+        let galley = WidgetText::from(back).into_galley(
+            ui,
+            Some(TextWrapMode::Extend),
+            f32::INFINITY,
+            TextStyle::Body,
+        );
+
+        let spacing = ui.spacing().item_spacing.x;
+        let size = vec2(
+            self.chevron_size.x + spacing + galley.size().x,
+            self.chevron_size.y.max(galley.size().y),
+        );
+        let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+        response.widget_info(|| {
+            WidgetInfo::labeled(
+                WidgetType::Button,
+                ui.is_enabled(),
+                format!("Back to {}", galley.text()),
+            )
+        });
 
-    let min = r.rect.min;
-    let max = r.rect.max;
+        // the chevron is on the leading side of the text
+        let (chevron_x, text_x) = match direction {
+            LayoutDirection::LeftToRight => (rect.left(), rect.right() - galley.size().x),
+            LayoutDirection::RightToLeft => (rect.right() - self.chevron_size.x, rect.left()),
+        };
+        let chevron_rect = Rect::from_min_size(
+            Pos2::new(chevron_x, rect.center().y - self.chevron_size.y / 2.0),
+            self.chevron_size,
+        );
+        let text_pos = Pos2::new(text_x, rect.center().y - galley.size().y / 2.0);
+
+        chevron(ui.painter(), chevron_rect, self.padding, stroke, direction);
+        ui.painter()
+            .galley(text_pos, galley, ui.visuals().text_color());
+
+        response
+    }
+}
 
-    let apex = Pos2::new(min.x + pad, min.y + size.y / 2.0);
-    let top = Pos2::new(max.x - pad, min.y + pad);
-    let bottom = Pos2::new(max.x - pad, max.y - pad);
+fn chevron(
+    painter: &egui::Painter,
+    rect: Rect,
+    pad: f32,
+    stroke: impl Into<Stroke>,
+    direction: LayoutDirection,
+) {
+    let size = rect.size();
+    let min = rect.min;
+    let max = rect.max;
+
+    // the chevron points back towards the leading edge
+    let (apex_x, arms_x) = match direction {
+        LayoutDirection::LeftToRight => (min.x + pad, max.x - pad),
+        LayoutDirection::RightToLeft => (max.x - pad, min.x + pad),
+    };
+
+    let apex = Pos2::new(apex_x, min.y + size.y / 2.0);
+    let top = Pos2::new(arms_x, min.y + pad);
+    let bottom = Pos2::new(arms_x, max.y - pad);
 
     let stroke = stroke.into();
     painter.line_segment([apex, top], stroke);
     painter.line_segment([apex, bottom], stroke);
-
-    r
 }
@@ -1,10 +1,11 @@
-use egui::Pos2;
+use egui::{Pos2, Vec2};
 
 use bitflags::bitflags;
 use tracing::trace;
 
 bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct DragDirection: u8 {
         const LeftToRight = 0b0001;
         const RightToLeft = 0b0010;
@@ -19,8 +20,13 @@ pub(crate) struct Drag {
     offset_from_rest: f32,
     threshold: f32, // if offset_from_rest is ABOVE threshold when drag is released, that means the drag MEETS the threshold
     angle: DragAngle,
+    fling_direction: DragDirection, // the direction in which a fling moves away from rest
+    min_fling_velocity: f32,        // points per second
 }
 
+/// Releasing faster than this counts as a fling, in points per second
+const MIN_FLING_VELOCITY: f32 = 500.0;
+
 impl Drag {
     pub(crate) fn new(
         id: egui::Id,
@@ -37,9 +43,18 @@ impl Drag {
             offset_from_rest,
             threshold,
             angle,
+            fling_direction: direction,
+            min_fling_velocity: MIN_FLING_VELOCITY,
         }
     }
 
+    /// The direction in which a fling moves away from rest. Defaults to
+    /// the drag direction, which is ambiguous when dragging both ways.
+    pub(crate) fn fling_direction(mut self, direction: DragDirection) -> Self {
+        self.fling_direction = direction;
+        self
+    }
+
     pub(crate) fn handle(
         &mut self,
         ui: &mut egui::Ui,
@@ -67,6 +82,7 @@ impl Drag {
             if can_take_drag_id || dragged_id == self.id {
                 if self.handle_dragging(ui, dragged_id, can_take_drag_id) {
                     trace!("got action DragAction::Dragging");
+                    track_velocity(ui.ctx());
                     resp = Some(DragAction::Dragging)
                 }
             } else if self.offset_from_rest > 0.0 {
@@ -86,9 +102,20 @@ impl Drag {
                 trace!("received drag stopped id and it is our Drag id");
                 if let Some(state) = get_state(ui.ctx()) {
                     resp = match self.get_direction(&state) {
-                        HandleDragDirection::CorrectDirection => Some(DragAction::DragReleased {
-                            threshold_met: self.offset_from_rest >= self.threshold,
-                        }),
+                        HandleDragDirection::CorrectDirection => {
+                            let velocity = ui.input(|i| i.pointer.velocity());
+                            // velocity can be zero when the frame rate is bad
+                            let velocity = if velocity == Vec2::ZERO {
+                                state.velocity
+                            } else {
+                                velocity
+                            };
+
+                            Some(DragAction::DragReleased {
+                                threshold_met: self.threshold_met(velocity),
+                                velocity: axis_value(self.direction, velocity),
+                            })
+                        }
                         HandleDragDirection::DirectionInconclusive => {
                             trace!("the direction is inconclusive");
                             Some(DragAction::DragUnrelated)
@@ -161,6 +188,7 @@ impl Drag {
             DragState {
                 start_pos: origin,
                 cur_direction: Some(cur_direction),
+                velocity: Vec2::ZERO,
             },
         );
         trace!("INSERTED DragState");
@@ -175,6 +203,25 @@ impl Drag {
         true
     }
 
+    /// A fast enough fling decides on its own, otherwise we look at how
+    /// far we were dragged
+    fn threshold_met(&self, velocity: Vec2) -> bool {
+        let sign = if self.fling_direction.contains(DragDirection::RightToLeft)
+            && !self.fling_direction.contains(DragDirection::LeftToRight)
+        {
+            -1.0
+        } else {
+            1.0
+        };
+        let fling = axis_value(self.fling_direction, velocity) * sign;
+
+        if fling.abs() >= self.min_fling_velocity {
+            fling > 0.0
+        } else {
+            self.offset_from_rest >= self.threshold
+        }
+    }
+
     fn get_direction(&self, state: &DragState) -> HandleDragDirection {
         let Some(cur_direction) = state.cur_direction else {
             return HandleDragDirection::DirectionInconclusive;
@@ -206,7 +253,11 @@ enum HandleDragDirection {
 #[derive(Debug, Clone)]
 pub enum DragAction {
     Dragging,
-    DragReleased { threshold_met: bool },
+    DragReleased {
+        threshold_met: bool,
+        /// pointer velocity along the drag axis, in points per second
+        velocity: f32,
+    },
     DragUnrelated,
 }
 
@@ -219,6 +270,22 @@ pub fn get_state(ctx: &egui::Context) -> Option<DragState> {
     ctx.data(|d| d.get_temp(id))
 }
 
+/// Remember the pointer velocity while dragging, in case it is not
+/// available on the frame the drag is released
+fn track_velocity(ctx: &egui::Context) {
+    let velocity = ctx.input(|i| i.pointer.velocity());
+    if velocity == Vec2::ZERO {
+        return;
+    }
+
+    ctx.data_mut(|d| {
+        if let Some(mut state) = d.get_temp::<DragState>(state_id()) {
+            state.velocity = velocity;
+            d.insert_temp(state_id(), state);
+        }
+    });
+}
+
 fn remove_state(ctx: &egui::Context) {
     ctx.data_mut(|d| d.remove::<DragState>(state_id()));
 }
@@ -227,6 +294,7 @@ fn remove_state(ctx: &egui::Context) {
 pub struct DragState {
     pub(crate) start_pos: Pos2,
     pub(crate) cur_direction: Option<DragDirection>,
+    pub(crate) velocity: Vec2,
 }
 
 /// Conclusively determine the direction the user meant to drag.
@@ -265,6 +333,24 @@ fn cur_direction(start: Pos2, cur_pos: Pos2, angle: DragAngle) -> Option<DragDir
     })
 }
 
+/// How far a drag has to go before releasing it counts
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DragThreshold {
+    /// An absolute distance in points
+    Absolute(f32),
+    /// A fraction of the size of the dragged content
+    Fraction(f32),
+}
+
+impl DragThreshold {
+    pub fn resolve(&self, extent: f32) -> f32 {
+        match *self {
+            DragThreshold::Absolute(distance) => distance,
+            DragThreshold::Fraction(fraction) => extent * fraction,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum DragAngle {
     Balanced,
@@ -293,10 +379,15 @@ impl DragParams {
 
 pub(crate) fn drag_delta(ui: &mut egui::Ui, direction: DragDirection) -> f32 {
     let delta = ui.input(|input| input.pointer.delta());
+    axis_value(direction, delta)
+}
+
+/// The component of `v` along the axis of `direction`
+fn axis_value(direction: DragDirection, v: Vec2) -> f32 {
     if direction.intersects(DragDirection::LeftToRight | DragDirection::RightToLeft) {
-        delta.x
+        v.x
     } else if direction.contains(DragDirection::Vertical) {
-        delta.y
+        v.y
     } else {
         0.0
     }
@@ -1,8 +1,11 @@
-use egui::{LayerId, Order};
+use egui::{Color32, LayerId, Order};
 
 use crate::{
+    a11y,
+    animation::Motion,
     drag::{DragAngle, DragParams},
-    render_bg, render_fg, Drag, DragDirection, NavAction, RouteResponse, State,
+    focus, nest, render_bg, render_fg, Animation, BackInput, Drag, DragDirection, LayerTransition,
+    LayoutDirection, NavAction, RouteResponse, Spring, State,
 };
 
 pub struct NavDrawer<'a, Route: Clone> {
@@ -14,6 +17,9 @@ pub struct NavDrawer<'a, Route: Clone> {
     returning: bool,
     drawer_focused: bool,
     use_drag: bool,
+    motion: Motion,
+    layout_direction: Option<LayoutDirection>,
+    back_input: BackInput,
 }
 
 impl<'a, Route: Clone> NavDrawer<'a, Route> {
@@ -27,9 +33,19 @@ impl<'a, Route: Clone> NavDrawer<'a, Route> {
             returning: false,
             drawer_focused: false,
             use_drag: true,
+            motion: Motion::default(),
+            layout_direction: None,
+            back_input: BackInput::empty(),
         }
     }
 
+    /// Keys and mouse buttons that close the open drawer, usually
+    /// [`BackInput::Escape`]. Off by default.
+    pub fn back_input(mut self, back_input: BackInput) -> Self {
+        self.back_input = back_input;
+        self
+    }
+
     pub fn opened_offset(mut self, drawer_end_x: f32) -> Self {
         self.drawer_end_offset = drawer_end_x;
         self
@@ -64,6 +80,26 @@ impl<'a, Route: Clone> NavDrawer<'a, Route> {
         self
     }
 
+    /// Use a timed animation with a fixed duration and easing for
+    /// opening and closing
+    pub fn animation(mut self, animation: Animation) -> Self {
+        self.motion = Motion::Timed(animation);
+        self
+    }
+
+    /// Use spring physics for opening and closing. This is the default.
+    pub fn spring(mut self, spring: Spring) -> Self {
+        self.motion = Motion::Spring(spring);
+        self
+    }
+
+    /// Which edge the drawer opens from. Detected from the ui's layout
+    /// when not set, right to left layouts open from the right.
+    pub fn layout_direction(mut self, direction: LayoutDirection) -> Self {
+        self.layout_direction = Some(direction);
+        self
+    }
+
     fn id(&self, ui: &egui::Ui) -> egui::Id {
         ui.id().with(("nav-drawer", self.id_source))
     }
@@ -95,46 +131,76 @@ impl<'a, Route: Clone> NavDrawer<'a, Route> {
         let id = self.id(ui);
         let mut state = State::load(ui.ctx(), id).unwrap_or_default();
 
-        let rest = 0.0;
-        let max = self.drawer_end_offset;
+        let direction = self
+            .layout_direction
+            .unwrap_or_else(|| LayoutDirection::from_ui(ui));
+        // offsets are signed, right to left drawers open with negative offsets
+        let sign = direction.sign();
 
-        let (drawer_rect, bg_rect) = ui
-            .available_rect_before_wrap()
-            .split_left_right_at_x(state.offset);
+        let rest = 0.0;
+        let max = sign * self.drawer_end_offset;
+
+        let avail_rect = ui.available_rect_before_wrap();
+        let (drawer_rect, bg_rect) = match direction {
+            LayoutDirection::LeftToRight => avail_rect.split_left_right_at_x(state.offset),
+            LayoutDirection::RightToLeft => {
+                let (bg_rect, drawer_rect) =
+                    avail_rect.split_left_right_at_x(avail_rect.right() + state.offset);
+                (drawer_rect, bg_rect)
+            }
+        };
 
         let drag_content_rect = ui.available_rect_before_wrap();
 
-        let can_take_drag_from = if state.offset == rest {
-            show_route(ui, self.bg_route).can_take_drag_from
-        } else {
-            let avail_rect = ui.available_rect_before_wrap();
-            let alpha = if state.offset <= rest {
-                None
+        let open = state.offset != rest;
+        let (mut can_take_drag_from, passed_drags) = nest::scope(&ui.ctx().clone(), id, || {
+            if !open {
+                return show_route(ui, self.bg_route).can_take_drag_from;
+            }
+
+            let scrim = if sign * state.offset <= rest {
+                Color32::TRANSPARENT
             } else {
-                let t = ((self.drawer_end_offset - state.offset) / self.drawer_end_offset)
+                let t = ((self.drawer_end_offset - state.offset.abs()) / self.drawer_end_offset)
                     .clamp(0.0, 1.0);
-                Some(((1.0 - t) * 200.0).round() as u8)
+                Color32::from_black_alpha(((1.0 - t) * 200.0).round() as u8)
             };
 
-            render_bg(ui, None, bg_rect, avail_rect, alpha, |ui| {
+            let transition = LayerTransition {
+                scrim,
+                ..LayerTransition::IDENTITY
+            };
+            render_bg(ui, ui.id(), transition, bg_rect, avail_rect, |ui| {
                 show_route(ui, self.bg_route).can_take_drag_from
             })
             .can_take_drag_from
-        };
+        });
+        can_take_drag_from.extend(passed_drags);
+
+        // nested navs at their root close the open drawer. With nothing
+        // to close, the return is passed on.
+        let mut returning = self.returning;
+        if nest::take_return(ui.ctx(), id) {
+            if open {
+                returning = true;
+            } else {
+                nest::pass_return(ui.ctx());
+            }
+        }
 
         let drag = if self.use_drag {
-            Some(Drag::new(
+            let drag = Drag::new(
                 self.drag_id(ui),
                 if self.drawer_focused {
                     DragDirection::all()
                 } else {
-                    DragDirection::LeftToRight
+                    direction.drag_direction()
                 },
                 drag_content_rect,
                 if self.drawer_focused {
-                    (state.offset - self.drawer_end_offset).abs()
+                    (state.offset - max).abs()
                 } else {
-                    state.offset
+                    state.offset.abs()
                 },
                 0.1,
                 if self.drawer_focused {
@@ -142,7 +208,14 @@ impl<'a, Route: Clone> NavDrawer<'a, Route> {
                 } else {
                     DragAngle::Custom(DragParams::new(5).ignore_x_width(48.0))
                 },
-            ))
+            );
+
+            Some(if self.drawer_focused {
+                // flinging the drawer closed moves it back to its edge
+                drag.fling_direction(direction.reverse_drag_direction())
+            } else {
+                drag
+            })
         } else {
             None
         };
@@ -151,8 +224,8 @@ impl<'a, Route: Clone> NavDrawer<'a, Route> {
             if state.action != Some(NavAction::Navigating) {
                 state.action = Some(NavAction::Navigating);
             }
-        } else if self.returning && !matches!(state.action, Some(NavAction::Returning(_))) {
-            state.offset = self.drawer_end_offset;
+        } else if returning && !matches!(state.action, Some(NavAction::Returning(_))) {
+            state.offset = max;
             state.action = Some(NavAction::Returning(crate::ReturnType::Click));
         }
 
@@ -168,7 +241,11 @@ impl<'a, Route: Clone> NavDrawer<'a, Route> {
 
             let nav_action = match action.clone() {
                 crate::drag::DragAction::Dragging => NavAction::Dragging,
-                crate::drag::DragAction::DragReleased { threshold_met } => {
+                crate::drag::DragAction::DragReleased {
+                    threshold_met,
+                    velocity,
+                } => {
+                    state.velocity = velocity;
                     if self.drawer_focused {
                         if threshold_met {
                             NavAction::Returning(crate::ReturnType::Drag)
@@ -190,7 +267,14 @@ impl<'a, Route: Clone> NavDrawer<'a, Route> {
         let offset = state.offset;
 
         if let Some(action) = state.action {
-            action.handle(ui, &mut state, DragDirection::LeftToRight, max, rest, true);
+            action.handle(
+                ui,
+                &mut state,
+                direction.drag_direction(),
+                max,
+                rest,
+                Some(self.motion),
+            );
         }
 
         if state.offset == rest {
@@ -207,23 +291,54 @@ impl<'a, Route: Clone> NavDrawer<'a, Route> {
             state.action = Some(NavAction::Returning(crate::ReturnType::Click));
         }
 
-        let translate = egui::vec2((offset - max).min(0.0), 0.0);
-        let clip_rect =
-            egui::Rect::from_min_size(drawer_rect.min, egui::vec2(max, drawer_rect.height()));
+        // a partially open drawer is shifted back towards its edge
+        let translate = egui::vec2(sign * (sign * (offset - max)).min(0.0), 0.0);
+        let clip_min = match direction {
+            LayoutDirection::LeftToRight => drawer_rect.min,
+            LayoutDirection::RightToLeft => egui::pos2(
+                drawer_rect.max.x - self.drawer_end_offset,
+                drawer_rect.min.y,
+            ),
+        };
+        let clip_rect = egui::Rect::from_min_size(
+            clip_min,
+            egui::vec2(self.drawer_end_offset, drawer_rect.height()),
+        );
 
+        let fg_layer = LayerId::new(Order::Foreground, id.with("fg"));
         let drawer_response = Some(
-            render_fg(
-                ui,
-                id.with("fg"),
-                LayerId::new(Order::Foreground, id.with("fg")),
-                Some(translate),
-                clip_rect,
-                clip_rect,
-                |ui| show_route(ui, self.drawer_route),
-            )
+            nest::scope(&ui.ctx().clone(), id, || {
+                render_fg(
+                    ui,
+                    id.with("fg"),
+                    fg_layer,
+                    LayerTransition {
+                        translation: translate,
+                        ..LayerTransition::IDENTITY
+                    },
+                    clip_rect,
+                    clip_rect,
+                    |ui| {
+                        let ctx = ui.ctx().clone();
+                        a11y::node(&ctx, id.with("dialog"), a11y::Role::Dialog, None, || {
+                            focus::anchor(ui, id.with("focus_anchor"));
+                            show_route(ui, self.drawer_route)
+                        })
+                    },
+                )
+            })
+            .0
             .response,
         );
 
+        focus::trap(ui.ctx(), fg_layer, id.with("focus_anchor"));
+
+        if self.back_input.pressed(ui.ctx())
+            && !matches!(state.action, Some(NavAction::Returning(_)))
+        {
+            state.action = Some(NavAction::Returning(crate::ReturnType::Key));
+        }
+
         state.store(ui.ctx(), id);
 
         DrawerResponse {
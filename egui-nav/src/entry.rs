@@ -0,0 +1,97 @@
+use egui::Id;
+
+/// Gives every route on the stack its own id scope, so things like
+/// scroll offsets stay with the route while it is covered, swiped back
+/// to, or brought back with forward history.
+///
+/// Routes don't have an identity of their own, so we track the entries
+/// by watching the length of the stack and the actions we hand out.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Entries {
+    /// one seed per route, bottom to top
+    seeds: Vec<u64>,
+    /// seeds of the routes we returned from, the next forward one is last
+    forward: Vec<u64>,
+    next: u64,
+}
+
+impl Entries {
+    pub(crate) fn load(ctx: &egui::Context, id: Id) -> Self {
+        ctx.data(|d| d.get_temp(id)).unwrap_or_default()
+    }
+
+    pub(crate) fn store(self, ctx: &egui::Context, id: Id) {
+        ctx.data_mut(|d| d.insert_temp(id, self));
+    }
+
+    fn fresh(&mut self) -> u64 {
+        self.next += 1;
+        self.next
+    }
+
+    /// Catch up with the routes the app pushed or popped since last frame
+    pub(crate) fn sync(&mut self, len: usize, has_forward: bool) {
+        if self.seeds.len() > len {
+            let popped = self.seeds.split_off(len);
+            self.forward.extend(popped.into_iter().rev());
+        }
+
+        if self.seeds.len() < len {
+            // a regular push starts a new branch of history
+            self.forward.clear();
+            while self.seeds.len() < len {
+                let seed = self.fresh();
+                self.seeds.push(seed);
+            }
+        }
+
+        if !has_forward {
+            self.forward.clear();
+        }
+    }
+
+    /// The top route was swapped for a new one without changing the
+    /// length of the stack
+    pub(crate) fn renew_top(&mut self) {
+        let seed = self.fresh();
+        if let Some(top) = self.seeds.last_mut() {
+            *top = seed;
+        }
+    }
+
+    /// The route below the top was removed after a replace
+    pub(crate) fn replaced(&mut self) {
+        let len = self.seeds.len();
+        if len > 1 {
+            self.seeds.remove(len - 2);
+        }
+    }
+
+    /// The forward route was pushed back on the stack
+    pub(crate) fn forwarded(&mut self) {
+        let seed = self.forward_seed();
+        self.forward.pop();
+        self.seeds.push(seed);
+    }
+
+    fn forward_seed(&mut self) -> u64 {
+        if let Some(seed) = self.forward.last() {
+            return *seed;
+        }
+
+        let seed = self.fresh();
+        self.forward.push(seed);
+        seed
+    }
+
+    /// The id scope of the route at `depth`. One past the top is the
+    /// forward route.
+    pub(crate) fn id(&mut self, base: Id, depth: usize) -> Id {
+        let seed = match self.seeds.get(depth) {
+            Some(seed) => *seed,
+            None => self.forward_seed(),
+        };
+
+        base.with(("entry", seed))
+    }
+}
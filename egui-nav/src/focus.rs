@@ -0,0 +1,89 @@
+use egui::{Id, LayerId, Rect, Sense, Vec2};
+
+/// An invisible widget at the start of a route that focus can be moved
+/// to when we don't know anything better. The next Tab lands on the
+/// first widget of the route.
+pub(crate) fn anchor(ui: &mut egui::Ui, id: Id) {
+    let rect = Rect::from_min_size(ui.max_rect().min, Vec2::ZERO);
+    ui.interact(rect, id, Sense::focusable_noninteractive());
+}
+
+/// The focused widget, if it is in `layer_id` and inside `rect`
+pub(crate) fn within(ctx: &egui::Context, layer_id: LayerId, rect: Rect) -> Option<Id> {
+    let focused = ctx.memory(|m| m.focused())?;
+    let response = ctx.read_response(focused)?;
+    (response.layer_id == layer_id && rect.intersects(response.rect)).then_some(focused)
+}
+
+/// Should we leave focus alone? We do when nothing is focused, or when
+/// the user is busy with a widget outside of `rect`. Widgets that are
+/// gone don't count.
+fn leave_alone(ctx: &egui::Context, rect: Rect) -> bool {
+    let Some(focused) = ctx.memory(|m| m.focused()) else {
+        return true;
+    };
+
+    ctx.read_response(focused)
+        .is_some_and(|response| !rect.intersects(response.rect))
+}
+
+/// Keep focus inside `layer_id` while a sheet or drawer is open. Focus
+/// that escapes goes back to `anchor`.
+pub(crate) fn trap(ctx: &egui::Context, layer_id: LayerId, anchor: Id) {
+    let Some(focused) = ctx.memory(|m| m.focused()) else {
+        return;
+    };
+
+    let inside = ctx
+        .read_response(focused)
+        .is_some_and(|response| response.layer_id == layer_id);
+    if !inside {
+        ctx.memory_mut(|m| m.request_focus(anchor));
+    }
+}
+
+/// The widgets that had focus in the routes below the top, so we can
+/// give it back when returning to them
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FocusStack {
+    saved: Vec<Option<Id>>,
+    /// moved to next frame, once the route we land on has been rendered
+    pending: Option<Id>,
+}
+
+impl FocusStack {
+    pub(crate) fn load(ctx: &egui::Context, id: Id) -> Self {
+        ctx.data(|d| d.get_temp(id)).unwrap_or_default()
+    }
+
+    pub(crate) fn store(self, ctx: &egui::Context, id: Id) {
+        ctx.data_mut(|d| d.insert_temp(id, self));
+    }
+
+    /// Remember the focused widget of the route at `depth`
+    pub(crate) fn save(&mut self, depth: usize, focused: Id) {
+        self.saved.resize(depth + 1, None);
+        self.saved[depth] = Some(focused);
+    }
+
+    /// Forget everything above `depth` and give back what was focused
+    /// there
+    pub(crate) fn take(&mut self, depth: usize) -> Option<Id> {
+        self.saved.truncate(depth + 1);
+        self.saved.get_mut(depth).and_then(Option::take)
+    }
+
+    /// Focus `target` next frame, see [`leave_alone`]
+    pub(crate) fn focus_next_frame(&mut self, ctx: &egui::Context, rect: Rect, target: Id) {
+        if !leave_alone(ctx, rect) {
+            self.pending = Some(target);
+        }
+    }
+
+    /// Apply a focus change from last frame
+    pub(crate) fn apply_pending(&mut self, ctx: &egui::Context) {
+        if let Some(target) = self.pending.take() {
+            ctx.memory_mut(|m| m.request_focus(target));
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Something outside of the app asking to move through history, like
+/// the browser's back and forward buttons
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HistoryEvent {
+    Back,
+    Forward,
+}
+
+/// Where a [`crate::NavStack`] records where the user has been, and
+/// hears about back and forward from outside of the app.
+///
+/// Pushes are reported right away, so the history already has the route
+/// that is sliding in. Pops, replaces and going forward are reported once
+/// their transitions finish, and a push that is interrupted by a pop is
+/// reported as popped again. Events only ask the stack to move: a
+/// [`HistoryEvent::Back`] is followed by a `pop` when the stack actually
+/// returned, or by `blocked` when it didn't. An adapter for the web
+/// `History` API pushes and pops browser entries on these calls, and
+/// skips the ones that answer its own `popstate` events.
+pub trait NavHistory<Route> {
+    /// `route` was pushed on top
+    fn push(&mut self, route: &Route);
+
+    /// The top route was swapped for `route`
+    fn replace(&mut self, route: &Route);
+
+    /// `levels` routes were popped
+    fn pop(&mut self, levels: usize);
+
+    /// The route we last popped was brought back
+    fn forward(&mut self);
+
+    /// A [`HistoryEvent::Back`] was refused, by a
+    /// [`crate::Nav::return_guard`] or because we were at the root. The
+    /// history went back already, go forward to the route we're on.
+    fn blocked(&mut self);
+
+    /// Events since the last call, oldest first
+    fn poll(&mut self) -> Vec<HistoryEvent>;
+}
+
+/// A [`NavHistory`] that keeps everything in memory. Handy for tests,
+/// or for back and forward buttons of your own. Clones share the same
+/// history, so keep one around after handing it to the stack.
+///
+/// Like a browser, [`MemoryHistory::back`] and
+/// [`MemoryHistory::go_forward`] move right away, and the stack's
+/// answer to them is skipped.
+#[derive(Clone, Debug)]
+pub struct MemoryHistory<Route> {
+    inner: Rc<RefCell<Entries<Route>>>,
+}
+
+#[derive(Debug)]
+struct Entries<Route> {
+    routes: Vec<Route>,
+    /// the route being shown, the ones after it can be gone forward to
+    current: usize,
+    events: Vec<HistoryEvent>,
+    /// moves we made that the stack hasn't answered yet
+    backs: usize,
+    forwards: usize,
+}
+
+impl<Route: Clone> MemoryHistory<Route> {
+    pub fn new(root: Route) -> Self {
+        let entries = Entries {
+            routes: vec![root],
+            current: 0,
+            events: Vec::new(),
+            backs: 0,
+            forwards: 0,
+        };
+        MemoryHistory {
+            inner: Rc::new(RefCell::new(entries)),
+        }
+    }
+
+    /// The routes from the root to the one being shown
+    pub fn routes(&self) -> Vec<Route> {
+        let entries = self.inner.borrow();
+        entries.routes[..=entries.current].to_vec()
+    }
+
+    /// The routes that can be gone forward to, next one first
+    pub fn forward_routes(&self) -> Vec<Route> {
+        let entries = self.inner.borrow();
+        entries.routes[entries.current + 1..].to_vec()
+    }
+
+    /// Go back and ask the stack to follow
+    pub fn back(&self) {
+        let mut entries = self.inner.borrow_mut();
+        if entries.current == 0 {
+            return;
+        }
+
+        entries.current -= 1;
+        entries.backs += 1;
+        entries.events.push(HistoryEvent::Back);
+    }
+
+    /// Go forward and ask the stack to follow
+    pub fn go_forward(&self) {
+        let mut entries = self.inner.borrow_mut();
+        if entries.current + 1 >= entries.routes.len() {
+            return;
+        }
+
+        entries.current += 1;
+        entries.forwards += 1;
+        entries.events.push(HistoryEvent::Forward);
+    }
+}
+
+impl<Route: Clone> NavHistory<Route> for MemoryHistory<Route> {
+    fn push(&mut self, route: &Route) {
+        let mut entries = self.inner.borrow_mut();
+        let len = entries.current + 1;
+        entries.routes.truncate(len);
+        entries.routes.push(route.clone());
+        entries.current = len;
+    }
+
+    fn replace(&mut self, route: &Route) {
+        let mut entries = self.inner.borrow_mut();
+        let current = entries.current;
+        entries.routes.truncate(current + 1);
+        entries.routes[current] = route.clone();
+    }
+
+    fn pop(&mut self, levels: usize) {
+        let mut entries = self.inner.borrow_mut();
+        let answered = levels.min(entries.backs);
+        entries.backs -= answered;
+        entries.current = entries.current.saturating_sub(levels - answered);
+    }
+
+    fn forward(&mut self) {
+        let mut entries = self.inner.borrow_mut();
+        if entries.forwards > 0 {
+            entries.forwards -= 1;
+            return;
+        }
+
+        entries.current = (entries.current + 1).min(entries.routes.len() - 1);
+    }
+
+    fn blocked(&mut self) {
+        let mut entries = self.inner.borrow_mut();
+        if entries.backs > 0 {
+            entries.backs -= 1;
+            entries.current = (entries.current + 1).min(entries.routes.len() - 1);
+        }
+    }
+
+    fn poll(&mut self) -> Vec<HistoryEvent> {
+        std::mem::take(&mut self.inner.borrow_mut().events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_history() {
+        let mut history = MemoryHistory::new("a");
+        history.push(&"b");
+        history.push(&"c");
+        assert_eq!(history.routes(), ["a", "b", "c"]);
+
+        history.pop(2);
+        assert_eq!(history.routes(), ["a"]);
+        assert_eq!(history.forward_routes(), ["b", "c"]);
+
+        history.forward();
+        assert_eq!(history.routes(), ["a", "b"]);
+        assert_eq!(history.forward_routes(), ["c"]);
+
+        // replacing drops the forward routes, like a push
+        history.replace(&"d");
+        assert_eq!(history.routes(), ["a", "d"]);
+        assert!(history.forward_routes().is_empty());
+
+        history.forward();
+        assert_eq!(history.routes(), ["a", "d"]);
+
+        history.pop(5);
+        assert_eq!(history.routes(), ["a"]);
+        history.push(&"e");
+        assert_eq!(history.routes(), ["a", "e"]);
+        assert!(history.forward_routes().is_empty());
+    }
+
+    #[test]
+    fn memory_history_events() {
+        let mut history = MemoryHistory::new("a");
+        let handle = history.clone();
+        history.push(&"b");
+
+        handle.back();
+        handle.back();
+        handle.go_forward();
+        assert_eq!(history.poll(), [HistoryEvent::Back, HistoryEvent::Forward]);
+        assert!(history.poll().is_empty());
+        assert_eq!(history.routes(), ["a", "b"]);
+
+        // the stack followed
+        history.pop(1);
+        history.forward();
+        assert_eq!(history.routes(), ["a", "b"]);
+    }
+
+    #[test]
+    fn memory_history_blocked() {
+        let mut history = MemoryHistory::new("a");
+        history.push(&"b");
+
+        history.back();
+        assert_eq!(history.routes(), ["a"]);
+        history.blocked();
+        assert_eq!(history.routes(), ["a", "b"]);
+
+        // nothing to undo
+        history.blocked();
+        assert_eq!(history.routes(), ["a", "b"]);
+    }
+}
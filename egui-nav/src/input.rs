@@ -0,0 +1,73 @@
+use bitflags::bitflags;
+use egui::{Key, Modifiers, PointerButton};
+
+bitflags! {
+    /// Keys and buttons that go back, in addition to swiping and the
+    /// title's back button.
+    ///
+    /// There is no browser back key: egui 0.31 doesn't have a
+    /// `Key::BrowserBack`, so that key isn't seen at all.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct BackInput: u8 {
+        const Escape = 0b0001;
+        const AltLeft = 0b0010;
+        /// The "back" extra mouse button
+        const MouseBack = 0b0100;
+    }
+}
+
+impl BackInput {
+    /// Was one of our inputs pressed this frame? A press is only ever
+    /// reported to the first component that asks for it, and never to
+    /// routes rendered behind something else.
+    pub(crate) fn pressed(self, ctx: &egui::Context) -> bool {
+        if self.is_empty() || is_behind(ctx) {
+            return false;
+        }
+
+        let key = ctx.input_mut(|i| {
+            (self.contains(BackInput::Escape) && i.consume_key(Modifiers::NONE, Key::Escape))
+                || (self.contains(BackInput::AltLeft)
+                    && i.consume_key(Modifiers::ALT, Key::ArrowLeft))
+        });
+
+        key || (self.contains(BackInput::MouseBack)
+            && ctx.input(|i| i.pointer.button_pressed(PointerButton::Extra1))
+            && claim_mouse_back(ctx))
+    }
+}
+
+fn mouse_back_id() -> egui::Id {
+    egui::Id::new("nav-back-mouse")
+}
+
+fn behind_id() -> egui::Id {
+    egui::Id::new("nav-back-behind")
+}
+
+/// Pointer buttons can't be consumed like keys, so remember which pass
+/// the mouse back button was last used in
+fn claim_mouse_back(ctx: &egui::Context) -> bool {
+    let pass = ctx.cumulative_pass_nr();
+    ctx.data_mut(|d| {
+        if d.get_temp::<u64>(mouse_back_id()) == Some(pass) {
+            false
+        } else {
+            d.insert_temp(mouse_back_id(), pass);
+            true
+        }
+    })
+}
+
+pub(crate) fn is_behind(ctx: &egui::Context) -> bool {
+    ctx.data(|d| d.get_temp::<usize>(behind_id())).unwrap_or(0) > 0
+}
+
+/// Render a route that is covered by another one. Back input is ignored
+/// while rendering it.
+pub(crate) fn behind<R>(ctx: &egui::Context, render: impl FnOnce() -> R) -> R {
+    ctx.data_mut(|d| *d.get_temp_mut_or_default::<usize>(behind_id()) += 1);
+    let res = render();
+    ctx.data_mut(|d| *d.get_temp_mut_or_default::<usize>(behind_id()) -= 1);
+    res
+}
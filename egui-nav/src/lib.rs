@@ -1,36 +1,112 @@
 use drag::Drag;
-use egui::{emath::TSTransform, vec2, LayerId, Order, Rect, Vec2};
+use egui::{vec2, Color32, LayerId, Order, Rect};
 
+mod a11y;
+mod animation;
 mod default_ui;
 mod drag;
 mod drawer;
+mod entry;
+mod focus;
+mod history;
+mod input;
+mod lifecycle;
+mod nest;
+mod path;
 mod popup_sheet;
+mod split;
+mod stack;
+mod tabs;
+mod transition;
 mod ui;
 mod util;
 
+pub use animation::{ease_in_out, ease_out_cubic, linear, Animation, Spring};
 pub use default_ui::{DefaultNavTitle, DefaultTitleResponse};
-pub use drag::DragDirection;
+pub use drag::{DragAngle, DragDirection, DragParams, DragThreshold};
 pub use drawer::{DrawerResponse, NavDrawer};
+pub use history::{HistoryEvent, MemoryHistory, NavHistory};
+pub use input::BackInput;
+pub use lifecycle::RouteLifecycle;
+pub use path::{parse_path, to_path, RoutePath};
 pub use popup_sheet::{Percent, PopupResponse, PopupSheet, Split};
-pub use ui::NavUiType;
-
-use crate::drag::{drag_delta, DragAngle};
+pub use split::{SplitNav, SplitResponse};
+pub use stack::NavStack;
+pub use tabs::NavTabs;
+pub use transition::{
+    CoverTransition, CrossFadeTransition, FadeThroughTransition, InstantTransition,
+    LayerTransition, NavTransition, PushTransition, SlideTransition, TransitionContext,
+    TransitionLayout, ZoomTransition,
+};
+pub use ui::{Axis, LayoutDirection, NavUiType};
+
+use crate::animation::{Motion, Tween};
+use crate::drag::drag_delta;
+use crate::entry::Entries;
+use crate::focus::FocusStack;
+use crate::lifecycle::{Lifecycle, Phase};
 
 pub struct Nav<'a, Route: Clone> {
     id_source: Option<egui::Id>,
     route: &'a [Route],
     navigating: bool,
+    replacing: bool,
     returning: bool,
+    forwarding: bool,
     animate_transitions: bool,
+    motion: Motion,
+    transition: &'a dyn NavTransition,
+    replace_transition: Option<&'a dyn NavTransition>,
+    swipe_edge: Option<f32>,
+    return_threshold: DragThreshold,
+    drag_angle: DragAngle,
+    layout_direction: Option<LayoutDirection>,
+    axis: Axis,
+    return_levels: usize,
+    forward: Option<&'a Route>,
+    back_input: BackInput,
+    route_label: Option<&'a dyn Fn(&Route) -> String>,
+    initial_focus: Option<InitialFocus<'a, Route>>,
+    route_key: Option<&'a dyn Fn(&Route) -> egui::Id>,
+    entry_id: Option<egui::Id>,
+    lifecycle: Option<Lifecycle<'a, Route>>,
+    return_guard: Option<&'a dyn Fn(&Route) -> ReturnGuard>,
+    /// set by containers that show part of the stack in a ui of their
+    /// own, so it keeps the state of the whole nav
+    fixed_id: Option<egui::Id>,
+    /// the routes of the whole nav below ours, shown by the container
+    below: &'a [Route],
+    /// set by [`NavStack`] the frame it swapped the top route in place
+    top_swapped: bool,
 }
 
+type InitialFocus<'a, Route> = &'a dyn Fn(&Route) -> Option<egui::Id>;
+
+static DEFAULT_TRANSITION: PushTransition = PushTransition::new();
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReturnType {
     Drag,
     Click,
+    Key,
 }
 
+/// Whether the top route can be returned from right now
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReturnGuard {
+    Allow,
+    /// Stay on the route. Swipes rubber band back.
+    Block,
+    /// Stay on the route, the app wants to ask first. Once the user
+    /// confirmed, return without the guard, see
+    /// [`NavStack::pop_confirmed`].
+    Confirm,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NavAction {
     /// We're returning to the previous view
     Returning(ReturnType),
@@ -41,14 +117,36 @@ pub enum NavAction {
     /// We're dragging the view. We're not making a return decision yet
     Dragging,
 
-    /// We've returning to the previous view. Pop your route!
-    Returned(ReturnType),
+    /// We've returned to a previous view. Pop this many routes!
+    Returned(ReturnType, usize),
 
     /// We've navigating to the next view.
     Navigating,
 
     /// We're finished navigating, push the route!
     Navigated,
+
+    /// We're replacing the top view with the one pushed on top of it
+    Replacing,
+
+    /// We're finished replacing, remove the route below the top!
+    Replaced,
+
+    /// We're bringing the forward route back on top
+    Forwarding,
+
+    /// We're finished going forward, push the forward route!
+    Forwarded,
+
+    /// The top route didn't let us return, see [`Nav::return_guard`].
+    /// Nothing was popped. Carries what the guard said, so a
+    /// [`ReturnGuard::Confirm`] can be told apart from a
+    /// [`ReturnGuard::Block`].
+    ReturnBlocked(ReturnType, ReturnGuard),
+
+    /// We were asked to return at the root, so the nav we're nested in
+    /// returns instead. Nothing was popped, stop returning!
+    ReturnDelegated,
 }
 
 impl NavAction {
@@ -57,9 +155,15 @@ impl NavAction {
             NavAction::Returning(_) => true,
             NavAction::Resetting => true,
             NavAction::Dragging => true,
-            NavAction::Returned(_) => false,
+            NavAction::Returned(..) => false,
             NavAction::Navigated => false,
             NavAction::Navigating => true,
+            NavAction::Replacing => true,
+            NavAction::Replaced => false,
+            NavAction::Forwarding => true,
+            NavAction::Forwarded => false,
+            NavAction::ReturnBlocked(..) => false,
+            NavAction::ReturnDelegated => false,
         }
     }
 
@@ -70,10 +174,13 @@ impl NavAction {
         drag_direction: DragDirection,
         navigated_offset: f32,
         returned_offset: f32,
-        animate: bool,
+        motion: Option<Motion>,
     ) {
         match self {
             NavAction::Dragging => {
+                // the pointer is in control, forget about any animation
+                state.tween = None;
+                state.velocity = 0.0;
                 state.offset += drag_delta(ui, drag_direction);
                 if navigated_offset < returned_offset {
                     if state.offset < navigated_offset {
@@ -99,54 +206,60 @@ impl NavAction {
                     }
                 }
             }
-            NavAction::Returned(_) => {
+            NavAction::Returned(..) => {
                 state.action = None;
             }
             NavAction::Navigated => {
                 state.action = None;
             }
-            NavAction::Navigating => {
-                if !animate {
-                    state.offset = navigated_offset;
-                    state.action = Some(NavAction::Navigated);
-                    return;
+            NavAction::Replaced => {
+                state.action = None;
+            }
+            NavAction::Forwarded => {
+                state.action = None;
+            }
+            NavAction::ReturnBlocked(..) => {
+                state.action = None;
+            }
+            NavAction::ReturnDelegated => {
+                state.action = None;
+            }
+            NavAction::Forwarding => {
+                if state.animate_to(ui, navigated_offset, motion) {
+                    state.action = Some(NavAction::Forwarded);
                 }
-                let left = state.offset > navigated_offset;
-                if let Some(offset) = spring_animate(state.offset, navigated_offset, left) {
-                    ui.ctx().request_repaint();
-                    state.offset = offset;
-                } else {
+            }
+            NavAction::Replacing => {
+                if state.animate_to(ui, navigated_offset, motion) {
+                    state.action = Some(NavAction::Replaced);
+                }
+            }
+            NavAction::Navigating => {
+                if state.animate_to(ui, navigated_offset, motion) {
                     state.action = Some(NavAction::Navigated);
                 }
             }
             NavAction::Returning(return_type) => {
-                if !animate {
-                    state.offset = returned_offset;
-                    state.action = Some(NavAction::Returned(return_type));
-                    return;
-                }
                 // We're returning, move the current view off to the
                 // returned_offset until the entire view is gone.
-
-                let left = state.offset > returned_offset;
-                if let Some(offset) = spring_animate(state.offset, returned_offset, left) {
-                    ui.ctx().request_repaint();
-                    state.offset = offset;
-                } else {
-                    state.offset = returned_offset;
-                    state.action = Some(NavAction::Returned(return_type));
+                if state.animate_to(ui, returned_offset, motion) {
+                    let levels = state.return_levels.max(1);
+                    state.action = Some(NavAction::Returned(return_type, levels));
                 }
             }
             NavAction::Resetting => {
                 // If we're resetting, animate the current offset
-                // back to the current view
-
-                let left = state.offset > navigated_offset;
-                if let Some(offset) = spring_animate(state.offset, navigated_offset, left) {
-                    ui.ctx().request_repaint();
-                    state.offset = offset;
+                // back to the current view. A cancelled forward swipe
+                // slides the forward route back out instead.
+                let target = if state.forward {
+                    returned_offset
                 } else {
-                    state.action = None
+                    navigated_offset
+                };
+                if state.animate_to(ui, target, motion) {
+                    state.action = None;
+                    state.forward = false;
+                    state.offset = navigated_offset;
                 }
             }
         }
@@ -154,26 +267,118 @@ impl NavAction {
 }
 
 #[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct State {
     offset: f32,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "undrag"))]
     action: Option<NavAction>,
     popped_min_rect: Option<Rect>,
+    tween: Option<Tween>,
+    /// how fast the offset is moving, in points per second
+    velocity: f32,
+    /// how many routes the current return pops
+    return_levels: usize,
+    /// the navigating flag we saw last frame
+    navigating: bool,
+    /// the foreground is the forward route, swiped in over the top route
+    forward: bool,
+}
+
+/// Nobody is dragging after a restart, let the route spring back
+#[cfg(feature = "serde")]
+fn undrag<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<NavAction>, D::Error> {
+    let action: Option<NavAction> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(action.map(|action| match action {
+        NavAction::Dragging => NavAction::Resetting,
+        action => action,
+    }))
 }
 
 impl State {
     fn is_transitioning(&self) -> bool {
         self.action.is_some_and(|s| s.is_transitioning())
     }
+
+    /// How many of `len` routes are behind the foreground. Multi level
+    /// returns show the target route right away.
+    fn bg_len(&self, len: usize) -> usize {
+        let levels = if matches!(self.action, Some(NavAction::Returning(_))) {
+            self.return_levels.max(1)
+        } else {
+            1
+        };
+        len - levels.min(len - 1)
+    }
+
+    /// Move the offset towards `target`, returns true once we're there.
+    /// With no motion we jump straight to the target.
+    fn animate_to(&mut self, ui: &egui::Ui, target: f32, motion: Option<Motion>) -> bool {
+        let dt = ui.input(|i| i.stable_dt).min(0.1);
+
+        let done = match motion {
+            None => true,
+            Some(Motion::Timed(animation)) => self.tween_to(target, dt, &animation),
+            Some(Motion::Spring(spring)) => {
+                self.tween = None;
+                spring.step(&mut self.offset, &mut self.velocity, target, dt)
+            }
+        };
+
+        if done {
+            self.offset = target;
+            self.tween = None;
+            self.velocity = 0.0;
+        } else {
+            ui.ctx().request_repaint();
+        }
+
+        done
+    }
+
+    fn tween_to(&mut self, target: f32, dt: f32, animation: &Animation) -> bool {
+        let mut tween = match self.tween {
+            Some(tween) if tween.continues(self.offset, target) => tween,
+            _ => {
+                // any velocity left over from a drag is used up here
+                let velocity = std::mem::take(&mut self.velocity);
+                Tween::new(self.offset, target, velocity, animation)
+            }
+        };
+
+        if let Some(offset) = tween.step(dt, animation) {
+            self.offset = offset;
+            self.tween = Some(tween);
+            false
+        } else {
+            true
+        }
+    }
 }
 
 impl State {
+    #[cfg(not(feature = "serde"))]
     pub fn load(ctx: &egui::Context, id: egui::Id) -> Option<Self> {
         ctx.data_mut(|d| d.get_temp(id))
     }
 
+    #[cfg(not(feature = "serde"))]
     pub fn store(self, ctx: &egui::Context, id: egui::Id) {
         ctx.data_mut(|d| d.insert_temp(id, self));
     }
+
+    /// With `serde`, a transition in flight picks up where it left off
+    /// after a restart
+    #[cfg(feature = "serde")]
+    pub fn load(ctx: &egui::Context, id: egui::Id) -> Option<Self> {
+        ctx.data_mut(|d| d.get_persisted(id))
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn store(self, ctx: &egui::Context, id: egui::Id) {
+        ctx.data_mut(|d| d.insert_persisted(id, self));
+    }
 }
 
 pub struct NavResponse<R> {
@@ -189,16 +394,60 @@ impl<'a, Route: Clone> Nav<'a, Route> {
         // the rest of the control, and it's easy to catchbb
         assert!(!route.is_empty(), "Nav routes cannot be empty");
         let navigating = false;
+        let replacing = false;
         let returning = false;
+        let forwarding = false;
         let id_source = None;
         let animate_transitions = true;
+        let motion = Motion::default();
+        let transition = &DEFAULT_TRANSITION;
+        let replace_transition = None;
+        let swipe_edge = None;
+        let return_threshold = DragThreshold::Fraction(0.25);
+        let drag_angle = DragAngle::Balanced;
+        let layout_direction = None;
+        let axis = Axis::Horizontal;
+        let return_levels = 1;
+        let forward = None;
+        let back_input = BackInput::empty();
+        let route_label = None;
+        let initial_focus = None;
+        let route_key = None;
+        let entry_id = None;
+        let lifecycle = None;
+        let return_guard = None;
+        let fixed_id = None;
+        let below = &[];
+        let top_swapped = false;
 
         Nav {
             id_source,
             navigating,
+            replacing,
             returning,
+            forwarding,
             route,
             animate_transitions,
+            motion,
+            transition,
+            replace_transition,
+            swipe_edge,
+            return_threshold,
+            drag_angle,
+            layout_direction,
+            axis,
+            return_levels,
+            forward,
+            back_input,
+            route_label,
+            initial_focus,
+            route_key,
+            entry_id,
+            lifecycle,
+            return_guard,
+            fixed_id,
+            below,
+            top_swapped,
         }
     }
 
@@ -208,26 +457,178 @@ impl<'a, Route: Clone> Nav<'a, Route> {
     }
 
     /// Call this when you have just pushed a new value to your route and
-    /// you want to animate to this new view
+    /// you want to animate to this new view. If a return is in flight it
+    /// is turned around from wherever it is. Clear this once you get
+    /// [`NavAction::Navigated`] or [`NavAction::Returned`].
     pub fn navigating(mut self, navigating: bool) -> Self {
         self.navigating = navigating;
         self
     }
 
+    /// Call this when you have just pushed the route that should replace
+    /// the current top. The old top stays visible behind it until
+    /// [`NavAction::Replaced`], then remove it from your routes.
+    pub fn replacing(mut self, replacing: bool) -> Self {
+        self.replacing = replacing;
+        self
+    }
+
     /// Call this when you have just invoked an action to return to the
-    /// previous view
+    /// previous view. A push that is still animating is reversed from
+    /// where it is, and takes precedence over `navigating`.
     pub fn returning(mut self, returning: bool) -> Self {
         self.returning = returning;
         self
     }
 
+    /// Call this to animate to the [`Nav::forward`] route, then push it
+    /// once you get [`NavAction::Forwarded`]
+    pub fn forwarding(mut self, forwarding: bool) -> Self {
+        self.forwarding = forwarding;
+        self
+    }
+
+    /// How many routes to go back when returning. The transition goes
+    /// straight from the top route to the target, and
+    /// [`NavAction::Returned`] reports how many routes to pop.
+    pub fn return_levels(mut self, levels: usize) -> Self {
+        self.return_levels = levels;
+        self
+    }
+
+    /// Return to the route at `index` in a single transition. Use together
+    /// with [`Nav::returning`].
+    pub fn return_to(self, index: usize) -> Self {
+        let levels = self.route.len().saturating_sub(index + 1);
+        self.return_levels(levels)
+    }
+
     pub fn animate_transitions(mut self, animate: bool) -> Self {
         self.animate_transitions = animate;
         self
     }
 
+    /// The route that was last returned from, if you keep one around.
+    /// Swiping towards the leading edge brings it back, and
+    /// [`NavAction::Forwarded`] tells you to push it again. Only
+    /// horizontal navs can swipe forward.
+    pub fn forward(mut self, route: Option<&'a Route>) -> Self {
+        self.forward = route;
+        self
+    }
+
+    /// Keys and mouse buttons that return to the previous route. Only the
+    /// innermost nav that can go back reacts to them. Off by default.
+    pub fn back_input(mut self, back_input: BackInput) -> Self {
+        self.back_input = back_input;
+        self
+    }
+
+    /// A name for each route. Screen readers use it to label the body and
+    /// announce the route we land on after a transition.
+    pub fn route_label(mut self, label: &'a dyn Fn(&Route) -> String) -> Self {
+        self.route_label = Some(label);
+        self
+    }
+
+    /// The widget to focus once a route has been pushed. Returning
+    /// focuses whatever had focus when the route was left. Without this,
+    /// focus goes to the start of the route and the next Tab lands on its
+    /// first widget. Focus is only moved if it was in the nav.
+    pub fn initial_focus(mut self, focus: InitialFocus<'a, Route>) -> Self {
+        self.initial_focus = Some(focus);
+        self
+    }
+
+    /// A key for each route. Entries are scoped by their key instead of
+    /// their place in the stack, so a route that is pushed again gets its
+    /// widget state back. Entries with the same key are still kept apart.
+    pub fn route_key(mut self, key: &'a dyn Fn(&Route) -> egui::Id) -> Self {
+        self.route_key = Some(key);
+        self
+    }
+
+    /// Ask the top route before returning from it. Refused returns are
+    /// reported as [`NavAction::ReturnBlocked`] along with the guard's
+    /// answer, so the `returning` flag can be cleared and a confirmation
+    /// shown for [`ReturnGuard::Confirm`].
+    pub fn return_guard(mut self, guard: &'a dyn Fn(&Route) -> ReturnGuard) -> Self {
+        self.return_guard = Some(guard);
+        self
+    }
+
+    /// Get told when routes appear and disappear, see [`RouteLifecycle`]
+    pub fn lifecycle(mut self, lifecycle: &'a dyn RouteLifecycle<Route>) -> Self
+    where
+        Route: Send + Sync + 'static,
+    {
+        self.lifecycle = Some(Lifecycle::new(lifecycle));
+        self
+    }
+
+    /// How routes are drawn while replacing the top route. Defaults to
+    /// the regular transition.
+    pub fn replace_transition(mut self, transition: &'a dyn NavTransition) -> Self {
+        self.replace_transition = Some(transition);
+        self
+    }
+
+    /// Use a timed animation with a fixed duration and easing for
+    /// transitions
+    pub fn animation(mut self, animation: Animation) -> Self {
+        self.motion = Motion::Timed(animation);
+        self
+    }
+
+    /// Use spring physics for transitions. This is the default.
+    pub fn spring(mut self, spring: Spring) -> Self {
+        self.motion = Motion::Spring(spring);
+        self
+    }
+
+    /// How routes are drawn while transitioning. Defaults to [`PushTransition`]
+    pub fn transition(mut self, transition: &'a dyn NavTransition) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// Only start a swipe back when the drag begins within `width` points
+    /// of the leading edge (the top edge for vertical navs), instead of
+    /// anywhere in the nav
+    pub fn swipe_edge(mut self, width: f32) -> Self {
+        self.swipe_edge = Some(width);
+        self
+    }
+
+    /// How far the route has to be swiped before releasing it returns.
+    /// Defaults to a quarter of the width.
+    pub fn return_threshold(mut self, threshold: DragThreshold) -> Self {
+        self.return_threshold = threshold;
+        self
+    }
+
+    /// How a swipe decides between horizontal and vertical
+    pub fn drag_angle(mut self, angle: DragAngle) -> Self {
+        self.drag_angle = angle;
+        self
+    }
+
+    /// Which way routes slide and swipe. Detected from the ui's layout
+    /// when not set.
+    pub fn layout_direction(mut self, direction: LayoutDirection) -> Self {
+        self.layout_direction = Some(direction);
+        self
+    }
+
+    /// Slide routes horizontally (the default) or vertically
+    pub fn axis(mut self, axis: Axis) -> Self {
+        self.axis = axis;
+        self
+    }
+
     fn id(&self, ui: &egui::Ui) -> egui::Id {
-        ui.id().with(("nav", self.id_source))
+        self.fixed_id
+            .unwrap_or_else(|| ui.id().with(("nav", self.id_source)))
     }
 
     pub fn drag_id(&self, ui: &egui::Ui) -> egui::Id {
@@ -238,6 +639,52 @@ impl<'a, Route: Clone> Nav<'a, Route> {
         self.route
     }
 
+    /// The id scope of the route being shown. Only set for the [`Nav`]
+    /// handed to `show_route`, where the body is already rendered inside
+    /// it. Use it for ids of things outside of the route's ui.
+    pub fn entry_id(&self) -> Option<egui::Id> {
+        self.entry_id
+    }
+
+    /// Does the top route let us return from it?
+    fn top_guard(&self) -> ReturnGuard {
+        self.return_guard
+            .map_or(ReturnGuard::Allow, |guard| guard(self.top()))
+    }
+
+    /// The id scope of the route at `depth` of `routes`
+    fn entry_scope(
+        &self,
+        entries: &mut Entries,
+        id: egui::Id,
+        routes: &[Route],
+        depth: usize,
+    ) -> egui::Id {
+        self.stack_scope(entries, id, routes, self.below.len() + depth)
+    }
+
+    /// The id scope of the route at `depth` of the whole nav, which is
+    /// the routes below ours followed by `routes`
+    fn stack_scope(
+        &self,
+        entries: &mut Entries,
+        id: egui::Id,
+        routes: &[Route],
+        depth: usize,
+    ) -> egui::Id {
+        let Some(route_key) = self.route_key else {
+            return entries.id(id, depth);
+        };
+
+        let stack: Vec<&Route> = self.below.iter().chain(routes).collect();
+        let key = route_key(stack[depth]);
+        let duplicates = stack[..depth]
+            .iter()
+            .filter(|route| route_key(route) == key)
+            .count();
+        id.with(("entry", key, duplicates))
+    }
+
     /// Nav guarantees there is at least one route element
     pub fn top(&self) -> &Route {
         &self.route[self.route.len() - 1]
@@ -277,56 +724,195 @@ impl<'a, Route: Clone> Nav<'a, Route> {
     {
         let id = self.id(ui);
         let mut state = State::load(ui.ctx(), id).unwrap_or_default();
+        let mut focus = FocusStack::load(ui.ctx(), id.with("focus"));
+        focus.apply_pending(ui.ctx());
 
         let drag_rect = ui.available_rect_before_wrap();
+        let direction = self
+            .layout_direction
+            .unwrap_or_else(|| LayoutDirection::from_ui(ui));
+        // offsets are signed and point to where the foreground goes when
+        // returning. That's the trailing edge, or the bottom for vertical navs
+        let (sign, dismiss, drag_direction, forward_direction) = match self.axis {
+            Axis::Horizontal => (
+                direction.sign(),
+                vec2(direction.sign(), 0.0),
+                direction.drag_direction(),
+                self.forward.map(|_| direction.reverse_drag_direction()),
+            ),
+            Axis::Vertical => (1.0, vec2(0.0, 1.0), DragDirection::Vertical, None),
+        };
+
+        let mut entries = Entries::load(ui.ctx(), id.with("entries"));
+        entries.sync(self.below.len() + self.route.len(), self.forward.is_some());
+        if (self.navigating && !state.navigating) || self.top_swapped {
+            // the pushed route may have taken the place of one we were
+            // still returning from, or of the one we were pushing
+            entries.renew_top();
+        }
 
-        let title_response = show_route(ui, NavUiType::Title, self).response;
+        let title_entry = self.entry_scope(&mut entries, id, self.route, self.route.len() - 1);
+        let title_nav = Nav {
+            entry_id: Some(title_entry),
+            ..*self
+        };
+        let title_response = ui
+            .push_id(title_entry, |ui| {
+                show_route(ui, NavUiType::Title, &title_nav).response
+            })
+            .inner;
         let available_rect = ui.available_rect_before_wrap();
+        let extent = self.axis.extent(available_rect);
+
+        // a nested nav at its root asked us to return last frame
+        let returning = self.returning || nest::take_return(ui.ctx(), id);
+
+        if state.forward
+            && (self.forward.is_none() || self.navigating || returning || self.replacing)
+        {
+            // the app moved on, drop the forward swipe
+            state.forward = false;
+            state.offset = 0.0;
+            state.action = None;
+        }
+
+        // a return the top route refused this frame
+        let mut blocked = None;
+        // a return we passed on to the nav we're nested in
+        let mut delegated = false;
+
+        // Transitions can interrupt each other. We keep the offset so the
+        // animation turns around from wherever it currently is.
+        if returning && self.route.len() == 1 {
+            // there is nothing below our root, a return goes to the nav
+            // we're nested in or nowhere
+            delegated = nest::pass_return(ui.ctx());
+        } else if returning {
+            if matches!(state.action, Some(NavAction::Returning(_))) {
+                // already on our way
+            } else {
+                match self.top_guard() {
+                    ReturnGuard::Allow => {
+                        state.action = Some(NavAction::Returning(ReturnType::Click));
+                    }
+                    guard => blocked = Some((ReturnType::Click, guard)),
+                }
+            }
+            state.return_levels = if self.returning {
+                // there is only so much stack to return through
+                self.return_levels.min(self.route.len() - 1)
+            } else {
+                1
+            };
+        } else if self.navigating {
+            match state.action {
+                Some(NavAction::Navigating) | Some(NavAction::Dragging) => {}
+
+                // the return we were asked for was withdrawn, or a swipe
+                // during the push was cancelled
+                Some(NavAction::Returning(ReturnType::Click)) | Some(NavAction::Resetting) => {
+                    state.action = Some(NavAction::Navigating);
+                }
 
-        // This should probably override other actions?
-        if self.navigating {
-            if state.action != Some(NavAction::Navigating) {
-                state.offset = available_rect.width();
-                state.action = Some(NavAction::Navigating);
+                // a swipe back wins over a stale navigating flag, unless
+                // the route was pushed while it was in flight
+                Some(NavAction::Returning(_)) => {
+                    if !state.navigating {
+                        state.action = Some(NavAction::Navigating);
+                    }
+                }
+
+                _ => {
+                    state.offset = sign * extent;
+                    state.action = Some(NavAction::Navigating);
+                }
             }
-        } else if self.returning && !matches!(state.action, Some(NavAction::Returning(_))) {
-            state.action = Some(NavAction::Returning(ReturnType::Click));
+        } else if self.replacing && state.action != Some(NavAction::Replacing) {
+            state.offset = sign * extent;
+            state.action = Some(NavAction::Replacing);
+        } else if self.forwarding
+            && self.forward.is_some()
+            && !state.forward
+            && !state.is_transitioning()
+        {
+            state.forward = true;
+            state.offset = sign * extent;
+            state.action = Some(NavAction::Forwarding);
         }
+        state.navigating = self.navigating;
 
-        // transition rendering
-        // behind transition layer
-        let transitioning = state.is_transitioning();
-        if transitioning {
-            let x_translate_amt = {
-                let min_rect = state.popped_min_rect.unwrap_or(available_rect);
-                let initial_shift = -min_rect.width() * 0.1;
-                let mut amt = initial_shift + springy(state.offset);
-                if amt > 0.0 {
-                    amt = 0.0;
+        let transition = match (state.action, self.replace_transition) {
+            (Some(NavAction::Replacing), Some(transition)) => transition,
+            _ => self.transition,
+        };
+        let motion = if self.animate_transitions && transition.animated() {
+            Some(self.motion)
+        } else {
+            None
+        };
+
+        // while swiping forward the forward route is drawn on top
+        let forward_routes: Vec<Route>;
+        let fg_nav = match self.forward {
+            Some(forward) if state.forward => {
+                forward_routes = self.route.iter().chain([forward]).cloned().collect();
+                Nav {
+                    route: &forward_routes,
+                    ..*self
                 }
+            }
+            _ => Nav { ..*self },
+        };
+        let fg_entry = self.entry_scope(&mut entries, id, fg_nav.route, fg_nav.route.len() - 1);
+        let fg_nav = Nav {
+            entry_id: Some(fg_entry),
+            ..fg_nav
+        };
 
-                amt
-            };
+        // transition rendering
+        let transitioning = state.is_transitioning();
+        let fg_layer = if transitioning {
+            // when transitioning, we need a new layer id otherwise the
+            // view transform will transform more things than we want
+            LayerId::new(Order::Foreground, ui.id().with("fg"))
+        } else {
+            // if we don't use the same layer id as the ui, then we
+            // will have scrollview MouseWheel scroll issues due to
+            // the way rect_contains_pointer works with overlapping
+            // layers
+            ui.layer_id()
+        };
 
-            let clip = Rect::from_min_size(
-                available_rect.min + egui::vec2(-x_translate_amt, 0.0),
-                vec2(state.offset, available_rect.max.y),
-            );
+        let layout = if transitioning {
+            let progress = 1.0 - (state.offset.abs() / extent).clamp(0.0, 1.0);
+            transition.layout(&TransitionContext {
+                progress,
+                direction: dismiss,
+                fg_rect: available_rect,
+                bg_rect: state.popped_min_rect.unwrap_or(available_rect),
+                fg_layer,
+                bg_layer: LayerId::new(Order::Background, ui.id()),
+            })
+        } else {
+            TransitionLayout::default()
+        };
 
-            let translate_vec = egui::vec2(x_translate_amt, 0.0);
+        // behind transition layer
+        if transitioning {
+            let bg_len = state.bg_len(fg_nav.route.len());
+            let bg_entry = self.entry_scope(&mut entries, id, fg_nav.route, bg_len - 1);
             let bg_nav = Nav {
-                route: &self.route[..self.route.len() - 1],
+                route: &fg_nav.route[..bg_len],
+                entry_id: Some(bg_entry),
                 ..*self
             };
 
-            let strength = 50.0; // fade strength (max is 255)
-            let alpha = ((1.0 - (state.offset / available_rect.width())) * strength) as u8;
             let bg_resp = render_bg(
                 ui,
-                Some(translate_vec),
-                clip,
+                bg_entry,
+                layout.bg,
+                layout.bg.clip_rect(available_rect),
                 available_rect,
-                Some(alpha),
                 |ui| show_route(ui, NavUiType::Body, &bg_nav).can_take_drag_from,
             );
 
@@ -334,36 +920,51 @@ impl<'a, Route: Clone> Nav<'a, Route> {
         };
 
         // foreground layer
-        let fg_resp = {
-            let clip = Rect::from_min_size(
-                available_rect.min,
-                vec2(
-                    available_rect.max.x - available_rect.min.x - state.offset,
-                    available_rect.max.y,
-                ),
-            );
-
-            let layer_id = if transitioning {
-                // when transitioning, we need a new layer id otherwise the
-                // view transform will transform more things than we want
-                LayerId::new(Order::Foreground, ui.id().with("fg"))
-            } else {
-                // if we don't use the same layer id as the ui, then we
-                // will have scrollview MouseWheel scroll issues due to
-                // the way rect_contains_pointer works with overlapping
-                // layers
-                ui.layer_id()
-            };
+        let (mut fg_resp, passed_drags) = nest::scope(&ui.ctx().clone(), id, || {
             render_fg(
                 ui,
-                ui.id(), // this must be ui.id() to not break scroll positions
-                layer_id,
-                Some(Vec2::new(state.offset, 0.0)),
-                clip,
+                // the id stays the same when the layer changes, so scroll
+                // positions don't break
+                fg_entry,
+                fg_layer,
+                layout.fg,
+                layout.fg.clip_rect(available_rect),
                 available_rect,
-                |ui| show_route(ui, NavUiType::Body, self),
+                |ui| {
+                    let label = self.route_label.map(|label| label(fg_nav.top()));
+                    let ctx = ui.ctx().clone();
+                    a11y::node(
+                        &ctx,
+                        id.with("body"),
+                        a11y::Role::Region,
+                        label.as_deref(),
+                        || {
+                            focus::anchor(ui, id.with("focus_anchor"));
+                            show_route(ui, NavUiType::Body, &fg_nav)
+                        },
+                    )
+                },
             )
-        };
+        });
+        fg_resp.can_take_drag_from.extend(passed_drags);
+
+        // checked after the foreground so nested navs get to go back first
+        if self.route.len() > 1
+            && self.back_input.pressed(ui.ctx())
+            && !state.forward
+            && !matches!(
+                state.action,
+                Some(NavAction::Returning(_)) | Some(NavAction::Dragging)
+            )
+        {
+            match self.top_guard() {
+                ReturnGuard::Allow => {
+                    state.action = Some(NavAction::Returning(ReturnType::Key));
+                    state.return_levels = 1;
+                }
+                guard => blocked = Some((ReturnType::Key, guard)),
+            }
+        }
 
         let ids_to_expose = if self.routes().len() > 1 {
             Vec::new()
@@ -371,25 +972,85 @@ impl<'a, Route: Clone> Nav<'a, Route> {
             fg_resp.can_take_drag_from.clone()
         };
 
-        // We only handle dragging when there is more than 1 route
+        // whatever we could take but don't, the nav we're nested in can
+        let mut unwanted_drags = fg_resp.can_take_drag_from.clone();
+        unwanted_drags.push(self.drag_id(ui));
+
+        // We only handle dragging when there is more than 1 route or a
+        // forward route. The route being replaced can't be swiped back to.
+        let mut drag_directions = DragDirection::empty();
         if self.route.len() > 1 {
+            drag_directions |= drag_direction;
+        }
+        if let Some(forward_direction) = forward_direction {
+            drag_directions |= forward_direction;
+        }
+        if !drag_directions.is_empty() && state.action != Some(NavAction::Replacing) {
             let content_rect = ui.available_rect_before_wrap();
+            let drag_rect = if let Some(width) = self.swipe_edge {
+                util::edge_strip(drag_rect, dismiss, width)
+            } else {
+                drag_rect
+            };
+
+            // a forward swipe is at rest when the forward route is gone
+            let (offset_from_rest, fling_direction) = match forward_direction {
+                Some(forward_direction) if state.forward => {
+                    (extent - state.offset.abs(), forward_direction)
+                }
+                _ => (state.offset.abs(), drag_direction),
+            };
+
             let mut cur_drag = Drag::new(
                 self.drag_id(ui),
-                DragDirection::LeftToRight,
+                drag_directions,
                 drag_rect,
-                state.offset,
-                content_rect.width() / 4.0,
-                DragAngle::Balanced,
-            );
+                offset_from_rest,
+                self.return_threshold
+                    .resolve(self.axis.extent(content_rect)),
+                self.drag_angle,
+            )
+            .fling_direction(fling_direction);
             if let Some(action) = cur_drag.handle(ui, fg_resp.can_take_drag_from) {
                 let nav_action = match action {
-                    crate::drag::DragAction::Dragging => NavAction::Dragging,
-                    crate::drag::DragAction::DragReleased { threshold_met } => {
-                        if threshold_met {
-                            NavAction::Returning(crate::ReturnType::Drag)
-                        } else {
+                    crate::drag::DragAction::Dragging => {
+                        let cur_direction = drag::get_state(ui.ctx()).and_then(|s| s.cur_direction);
+                        if state.action.is_none()
+                            && forward_direction.is_some()
+                            && cur_direction == forward_direction
+                        {
+                            // a forward swipe starts with the forward
+                            // route fully gone
+                            state.forward = true;
+                            state.offset = sign * extent;
+                        }
+                        NavAction::Dragging
+                    }
+                    crate::drag::DragAction::DragReleased {
+                        threshold_met,
+                        velocity,
+                    } => {
+                        state.velocity = velocity;
+                        if state.forward {
+                            if threshold_met {
+                                NavAction::Forwarding
+                            } else {
+                                NavAction::Resetting
+                            }
+                        } else if !threshold_met {
                             NavAction::Resetting
+                        } else {
+                            match self.top_guard() {
+                                ReturnGuard::Allow => {
+                                    // swiping back only ever goes back one level
+                                    state.return_levels = 1;
+                                    NavAction::Returning(crate::ReturnType::Drag)
+                                }
+                                guard => {
+                                    blocked = Some((ReturnType::Drag, guard));
+                                    NavAction::Resetting
+                                }
+                            }
                         }
                     }
                     crate::drag::DragAction::DragUnrelated => NavAction::Resetting,
@@ -398,76 +1059,133 @@ impl<'a, Route: Clone> Nav<'a, Route> {
             }
         }
 
+        if state.action != Some(NavAction::Dragging) {
+            nest::pass_drags(ui.ctx(), unwanted_drags);
+        }
+
         if let Some(action) = state.action {
-            action.handle(
-                ui,
-                &mut state,
-                DragDirection::LeftToRight,
-                0.0,
-                available_rect.width(),
-                self.animate_transitions,
-            );
+            action.handle(ui, &mut state, drag_direction, 0.0, sign * extent, motion);
         }
+        // the route on top once the transition is done, and its depth
+        let len = self.route.len();
+        let landed = match state.action {
+            Some(NavAction::Navigated) => Some((len - 1, self.top())),
+            Some(NavAction::Replaced) => Some((len.saturating_sub(2), self.top())),
+            Some(NavAction::Returned(_, levels)) => {
+                let depth = len - 1 - levels.min(len - 1);
+                Some((depth, &self.route[depth]))
+            }
+            Some(NavAction::Forwarded) => self.forward.map(|route| (len, route)),
+            _ => None,
+        };
+
+        if let Some((depth, route)) = landed {
+            if let Some(label) = self.route_label {
+                a11y::announce(ui.ctx(), id.with("announcement"), label(route));
+            }
+
+            let restored = focus
+                .take(self.below.len() + depth)
+                .filter(|_| matches!(state.action, Some(NavAction::Returned(..))));
+            let target = restored
+                .or_else(|| self.initial_focus.and_then(|initial| initial(route)))
+                .unwrap_or(id.with("focus_anchor"));
+            focus.focus_next_frame(ui.ctx(), available_rect, target);
+        } else if state.action.is_none() {
+            if let Some(focused) = focus::within(ui.ctx(), ui.layer_id(), available_rect) {
+                focus.save(self.below.len() + len - 1, focused);
+            }
+        }
+        focus.store(ui.ctx(), id.with("focus"));
+
+        if let Some(lifecycle) = self.lifecycle {
+            let routes = fg_nav.route;
+            let top = routes.len() - 1;
+            let mut visible = vec![];
+            for (depth, route) in self.below.iter().enumerate() {
+                let entry = self.stack_scope(&mut entries, id, routes, depth);
+                visible.push((entry, route, Phase::Appeared));
+            }
+            if let Some((depth, route)) = landed {
+                // a replaced route is still below the top until the
+                // entries catch up
+                let depth = if state.action == Some(NavAction::Replaced) {
+                    top
+                } else {
+                    depth
+                };
+                let entry = self.entry_scope(&mut entries, id, routes, depth);
+                visible.push((entry, route, Phase::Appeared));
+            } else if state.is_transitioning() && state.forward == (routes.len() > len) {
+                // a forward swipe that started this frame has no forward
+                // route in the foreground yet, we'll see it next frame
+                let leaving = match state.action {
+                    Some(NavAction::Returning(_)) => true,
+                    Some(NavAction::Dragging) => !state.forward,
+                    Some(NavAction::Resetting) => state.forward,
+                    _ => false,
+                };
+                let (fg_phase, bg_phase) = if leaving {
+                    (Phase::Disappearing, Phase::Appearing)
+                } else {
+                    (Phase::Appearing, Phase::Disappearing)
+                };
+                visible.push((fg_entry, &routes[top], fg_phase));
+
+                let bg = state.bg_len(routes.len()) - 1;
+                if bg != top {
+                    let bg_entry = self.entry_scope(&mut entries, id, routes, bg);
+                    visible.push((bg_entry, &routes[bg], bg_phase));
+                }
+            } else {
+                visible.push((fg_entry, &routes[top], Phase::Appeared));
+            }
+            lifecycle.update(ui.ctx(), id.with("lifecycle"), visible);
+        }
+        a11y::live_region(ui.ctx(), id.with("announcement"));
+
         if matches!(
             state.action,
-            Some(NavAction::Returned(_)) | Some(NavAction::Navigated)
+            Some(NavAction::Returned(..))
+                | Some(NavAction::Navigated)
+                | Some(NavAction::Replaced)
+                | Some(NavAction::Forwarded)
         ) {
             state.offset = 0.0;
+            state.return_levels = 0;
+            state.forward = false;
         }
 
+        match state.action {
+            Some(NavAction::Replaced) => entries.replaced(),
+            Some(NavAction::Forwarded) => entries.forwarded(),
+            _ => {}
+        }
+        entries.store(ui.ctx(), id.with("entries"));
+
         state.store(ui.ctx(), id);
 
         NavResponse {
             response: fg_resp.response,
             title_response,
-            action: state.action,
+            action: blocked
+                .map(|(typ, guard)| NavAction::ReturnBlocked(typ, guard))
+                .or(delegated.then_some(NavAction::ReturnDelegated))
+                .or(state.action),
             can_take_drag_from: ids_to_expose,
         }
     }
 }
 
-fn springy(offset: f32) -> f32 {
-    (offset.abs() * 0.3).max(0.2)
-}
-
-fn spring_animate(offset: f32, target: f32, left: bool) -> Option<f32> {
-    // nothing left to animate, user released drag beyond target
-    if (left && offset <= target) || (!left && offset >= target) {
-        return None;
-    }
-
-    let abs_offset = (offset - target).abs();
-    if abs_offset > 0.1 {
-        // need some margin of error
-        // some margin of error is needed
-        let sgn = (offset - target).signum();
-        let amt = springy(abs_offset);
-        let adj = amt * (if left { -1.0 } else { 1.0 });
-        let adjusted = offset + adj;
-
-        // if adjusting will flip a sign, then just set to 0
-        if (offset - adj - target).signum() != sgn {
-            None
-        } else {
-            Some(adjusted)
-        }
-    } else {
-        // we've reset, we're not in any specific state anymore
-        None
-    }
-}
-
 pub(crate) fn render_bg(
     ui: &mut egui::Ui,
-    translate_vec: Option<egui::Vec2>, // whether to translate the rendered route
-    clip: egui::Rect,                  // rect that should be clipped
-    available_rect: egui::Rect,        // rect of viewing area
-    alpha: Option<u8>,
+    id: egui::Id,
+    transition: LayerTransition, // how to draw the rendered route
+    clip: egui::Rect,            // rect that should be clipped
+    available_rect: egui::Rect,  // rect of viewing area
     mut render_route: impl FnMut(&mut egui::Ui) -> Vec<egui::Id>,
 ) -> RenderBgResponse {
-    let id = ui.id();
-
-    let layer_id = LayerId::new(Order::Background, id);
+    let layer_id = LayerId::new(Order::Background, ui.id());
     let mut ui = egui::Ui::new(
         ui.ctx().clone(),
         id,
@@ -476,34 +1194,20 @@ pub(crate) fn render_bg(
             .max_rect(available_rect),
     );
     ui.set_clip_rect(clip);
+    ui.set_opacity(transition.opacity);
 
-    let can_take_drag_from = render_route(&mut ui);
+    let ctx = ui.ctx().clone();
+    let can_take_drag_from = input::behind(&ctx, || render_route(&mut ui));
 
     let res = ui.min_rect();
 
-    if let Some(alpha) = alpha {
-        let fade_color = egui::Color32::from_black_alpha(alpha);
-
+    if transition.scrim != Color32::TRANSPARENT {
         ui.painter()
-            .rect_filled(clip, egui::CornerRadius::default(), fade_color);
-    }
-
-    let Some(translate_vec) = translate_vec else {
-        return RenderBgResponse {
-            rect: res,
-            can_take_drag_from,
-        };
-    };
-
-    if translate_vec == Vec2::ZERO {
-        return RenderBgResponse {
-            rect: res,
-            can_take_drag_from,
-        };
+            .rect_filled(clip, egui::CornerRadius::default(), transition.scrim);
     }
 
     ui.ctx()
-        .transform_layer_shapes(ui.layer_id(), TSTransform::from_translation(translate_vec));
+        .transform_layer_shapes(ui.layer_id(), transition.transform(available_rect));
 
     RenderBgResponse {
         rect: res,
@@ -520,7 +1224,7 @@ pub(crate) fn render_fg<R>(
     ui: &mut egui::Ui,
     id: egui::Id,
     layer_id: LayerId,
-    translate_vec: Option<egui::Vec2>, // whether to translate the rendered route
+    transition: LayerTransition, // how to draw the rendered route
     clip: egui::Rect,
     available_rect: egui::Rect,
     mut render_route: impl FnMut(&mut egui::Ui) -> RouteResponse<R>,
@@ -533,21 +1237,17 @@ pub(crate) fn render_fg<R>(
             .max_rect(available_rect),
     );
     ui.set_clip_rect(clip);
+    ui.set_opacity(transition.opacity);
 
     let res = render_route(&mut ui);
 
-    let Some(translate_vec) = translate_vec else {
-        return res;
-    };
-
-    if translate_vec == Vec2::ZERO {
-        return res;
+    if transition.scrim != Color32::TRANSPARENT {
+        ui.painter()
+            .rect_filled(clip, egui::CornerRadius::default(), transition.scrim);
     }
 
-    ui.ctx().transform_layer_shapes(
-        ui.layer_id(),
-        egui::emath::TSTransform::from_translation(translate_vec),
-    );
+    ui.ctx()
+        .transform_layer_shapes(ui.layer_id(), transition.transform(available_rect));
 
     res
 }
@@ -556,3 +1256,92 @@ pub struct RouteResponse<R> {
     pub response: R,
     pub can_take_drag_from: Vec<egui::Id>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Headless frames, 1/60th of a second apart
+    pub(crate) struct Frames {
+        ctx: egui::Context,
+        time: f64,
+    }
+
+    impl Frames {
+        pub(crate) fn new() -> Self {
+            Frames {
+                ctx: egui::Context::default(),
+                time: 0.0,
+            }
+        }
+
+        pub(crate) fn run(&mut self, frames: usize, mut show: impl FnMut(&mut egui::Ui)) {
+            for _ in 0..frames {
+                self.frame(vec![], &mut show);
+            }
+        }
+
+        /// Run a frame where `key` is pressed
+        pub(crate) fn press(&mut self, key: egui::Key, show: impl FnMut(&mut egui::Ui)) {
+            let event = egui::Event::Key {
+                key,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers: egui::Modifiers::NONE,
+            };
+            self.frame(vec![event], show);
+        }
+
+        fn frame(&mut self, events: Vec<egui::Event>, mut show: impl FnMut(&mut egui::Ui)) {
+            self.time += 1.0 / 60.0;
+            let input = egui::RawInput {
+                time: Some(self.time),
+                screen_rect: Some(Rect::from_min_size(egui::Pos2::ZERO, vec2(1000.0, 800.0))),
+                events,
+                ..Default::default()
+            };
+            let _ = self.ctx.run(input, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| show(ui));
+            });
+        }
+    }
+
+    fn blank(_ui: &mut egui::Ui, _typ: NavUiType, _nav: &Nav<&str>) -> RouteResponse<()> {
+        RouteResponse {
+            response: (),
+            can_take_drag_from: vec![],
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn restored_drags_spring_back() {
+        let state = State {
+            offset: 120.0,
+            action: Some(NavAction::Dragging),
+            ..Default::default()
+        };
+
+        let saved = ron::to_string(&state).unwrap();
+        let restored: State = ron::from_str(&saved).unwrap();
+        assert_eq!(restored.action, Some(NavAction::Resetting));
+        assert_eq!(restored.offset, 120.0);
+    }
+
+    #[test]
+    fn multi_level_returns_stop_at_the_root() {
+        let mut frames = Frames::new();
+        let routes = ["a", "b", "c"];
+        let mut actions = vec![];
+        frames.run(120, |ui| {
+            let nav = Nav::new(&routes).returning(true).return_levels(5);
+            actions.extend(nav.show(ui, blank).action);
+        });
+
+        assert!(actions.contains(&NavAction::Returned(ReturnType::Click, 2)));
+        assert!(!actions
+            .iter()
+            .any(|action| matches!(action, NavAction::Returned(_, levels) if *levels != 2)));
+    }
+}
@@ -0,0 +1,148 @@
+/// Hooks for when routes come and go. Each entry on the stack gets
+/// `will_appear` before it is shown and `did_disappear` once it is gone,
+/// with `did_appear` and `will_disappear` in between as transitions
+/// settle or start. An interactive swipe that ends up resetting calls
+/// the hooks in reverse, so every `will_` is followed by its `did_` or
+/// the opposite `will_`.
+///
+/// `entry` is the id scope of the stack entry, see [`crate::Nav::entry_id`].
+pub trait RouteLifecycle<Route> {
+    fn will_appear(&self, _route: &Route, _entry: egui::Id) {}
+    fn did_appear(&self, _route: &Route, _entry: egui::Id) {}
+    fn will_disappear(&self, _route: &Route, _entry: egui::Id) {}
+    fn did_disappear(&self, _route: &Route, _entry: egui::Id) {}
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Phase {
+    Appearing,
+    Appeared,
+    Disappearing,
+}
+
+type Update<Route> =
+    fn(&egui::Context, egui::Id, &dyn RouteLifecycle<Route>, Vec<(egui::Id, &Route, Phase)>);
+
+/// The hooks of a nav, along with a way to remember the phase of each
+/// entry between frames. That needs routes we can keep around, which
+/// only [`Lifecycle::new`] knows about.
+pub(crate) struct Lifecycle<'a, Route> {
+    hooks: &'a dyn RouteLifecycle<Route>,
+    update: Update<Route>,
+}
+
+impl<Route> Clone for Lifecycle<'_, Route> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Route> Copy for Lifecycle<'_, Route> {}
+
+impl<'a, Route: Clone + Send + Sync + 'static> Lifecycle<'a, Route> {
+    pub(crate) fn new(hooks: &'a dyn RouteLifecycle<Route>) -> Self {
+        Lifecycle {
+            hooks,
+            update: update::<Route>,
+        }
+    }
+}
+
+/// A [`Lifecycle`] that owns its hooks, for navs that outlive a frame
+pub(crate) struct OwnedLifecycle<Route> {
+    hooks: Box<dyn RouteLifecycle<Route>>,
+    update: Update<Route>,
+}
+
+impl<Route: Clone + Send + Sync + 'static> OwnedLifecycle<Route> {
+    pub(crate) fn new(hooks: impl RouteLifecycle<Route> + 'static) -> Self {
+        OwnedLifecycle {
+            hooks: Box::new(hooks),
+            update: update::<Route>,
+        }
+    }
+}
+
+impl<Route> OwnedLifecycle<Route> {
+    pub(crate) fn get(&self) -> Lifecycle<'_, Route> {
+        Lifecycle {
+            hooks: self.hooks.as_ref(),
+            update: self.update,
+        }
+    }
+}
+
+impl<Route> Lifecycle<'_, Route> {
+    /// Call the hooks for every entry whose phase changed since last
+    /// frame. Entries that aren't `visible` are gone.
+    pub(crate) fn update(
+        &self,
+        ctx: &egui::Context,
+        id: egui::Id,
+        visible: Vec<(egui::Id, &Route, Phase)>,
+    ) {
+        (self.update)(ctx, id, self.hooks, visible)
+    }
+}
+
+fn update<Route: Clone + Send + Sync + 'static>(
+    ctx: &egui::Context,
+    id: egui::Id,
+    hooks: &dyn RouteLifecycle<Route>,
+    visible: Vec<(egui::Id, &Route, Phase)>,
+) {
+    let previous: Vec<(egui::Id, Route, Phase)> = ctx.data(|d| d.get_temp(id)).unwrap_or_default();
+
+    for (entry, route, phase) in &previous {
+        if visible.iter().any(|(e, ..)| e == entry) {
+            continue;
+        }
+
+        if *phase != Phase::Disappearing {
+            hooks.will_disappear(route, *entry);
+        }
+        hooks.did_disappear(route, *entry);
+    }
+
+    let mut current = Vec::with_capacity(visible.len());
+    for (entry, route, phase) in visible {
+        let before = previous
+            .iter()
+            .find(|(e, ..)| *e == entry)
+            .map(|(.., phase)| *phase);
+
+        let phase = match (before, phase) {
+            // starting to move doesn't make a shown route new again
+            (Some(Phase::Appeared), Phase::Appearing) => Phase::Appeared,
+
+            (before, phase) if before == Some(phase) => phase,
+
+            (before, Phase::Appearing) => {
+                if before != Some(Phase::Appeared) {
+                    hooks.will_appear(route, entry);
+                }
+                phase
+            }
+
+            (before, Phase::Appeared) => {
+                if before != Some(Phase::Appearing) {
+                    hooks.will_appear(route, entry);
+                }
+                hooks.did_appear(route, entry);
+                phase
+            }
+
+            (before, Phase::Disappearing) => {
+                if before.is_none() {
+                    hooks.will_appear(route, entry);
+                }
+                hooks.will_disappear(route, entry);
+                phase
+            }
+        };
+
+        current.push((entry, route.clone(), phase));
+    }
+
+    ctx.data_mut(|d| d.insert_temp(id, current));
+}
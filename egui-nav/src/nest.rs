@@ -0,0 +1,80 @@
+use egui::Id;
+
+use crate::input;
+
+/// A nav, drawer or sheet rendering its routes. Navs nested inside of
+/// it pass it what they can't use themselves: drags they didn't take
+/// and returns while they are at their root.
+#[derive(Clone, Debug)]
+struct Scope {
+    id: Id,
+    drag_ids: Vec<Id>,
+}
+
+fn scopes_id() -> Id {
+    Id::new("nav-scopes")
+}
+
+fn passed_return_id(id: Id) -> Id {
+    id.with("passed-return")
+}
+
+/// Render the routes of the nav at `id`. Returns the drag ids nested
+/// navs passed up while rendering.
+pub(crate) fn scope<R>(ctx: &egui::Context, id: Id, render: impl FnOnce() -> R) -> (R, Vec<Id>) {
+    let scope = Scope {
+        id,
+        drag_ids: Vec::new(),
+    };
+    ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<Vec<Scope>>(scopes_id())
+            .push(scope)
+    });
+    let res = render();
+    let scope = ctx.data_mut(|d| d.get_temp_mut_or_default::<Vec<Scope>>(scopes_id()).pop());
+
+    (res, scope.map(|scope| scope.drag_ids).unwrap_or_default())
+}
+
+/// Let the nav we're nested in take drags we don't want
+pub(crate) fn pass_drags(ctx: &egui::Context, drag_ids: impl IntoIterator<Item = Id>) {
+    // covered routes don't get to drag anything
+    if input::is_behind(ctx) {
+        return;
+    }
+
+    ctx.data_mut(|d| {
+        if let Some(scope) = d
+            .get_temp_mut_or_default::<Vec<Scope>>(scopes_id())
+            .last_mut()
+        {
+            scope.drag_ids.extend(drag_ids);
+        }
+    });
+}
+
+/// Have the nav we're nested in go back instead of us, starting next
+/// frame. Returns false when we aren't nested.
+pub(crate) fn pass_return(ctx: &egui::Context) -> bool {
+    if input::is_behind(ctx) {
+        return false;
+    }
+
+    let parent = ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<Vec<Scope>>(scopes_id())
+            .last()
+            .map(|scope| scope.id)
+    });
+    let Some(parent) = parent else {
+        return false;
+    };
+
+    ctx.data_mut(|d| d.insert_temp(passed_return_id(parent), true));
+    true
+}
+
+/// Did a nested nav pass a return to the nav at `id`?
+pub(crate) fn take_return(ctx: &egui::Context, id: Id) -> bool {
+    ctx.data_mut(|d| d.remove_temp::<bool>(passed_return_id(id)))
+        .unwrap_or(false)
+}
@@ -0,0 +1,148 @@
+/// Routes that can be written as part of a path, so a stack can be
+/// turned into something like `/home/thread/abc/profile/xyz` and back.
+///
+/// A segment may span several `/` separated parts, like `thread/abc`.
+/// Parts can't be empty.
+pub trait RoutePath: Sized {
+    fn to_segment(&self) -> String;
+    fn from_segment(segment: &str) -> Option<Self>;
+}
+
+/// The path of a stack of routes, root first
+pub fn to_path<Route: RoutePath>(routes: &[Route]) -> String {
+    let mut path = String::new();
+    for route in routes {
+        path.push('/');
+        path.push_str(&route.to_segment());
+    }
+    path
+}
+
+/// Parse a path made with [`to_path`]. Each route takes the fewest parts
+/// that [`RoutePath::from_segment`] accepts while still letting the rest
+/// of the path parse. Returns `None` if the path can't be split into
+/// routes, or if there are no routes at all.
+pub fn parse_path<Route: RoutePath>(path: &str) -> Option<Vec<Route>> {
+    let parts: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+    if parts.is_empty() {
+        return None;
+    }
+
+    // the parts joined again without empty ones, and where each starts
+    let joined = parts.join("/");
+    let mut starts = Vec::with_capacity(parts.len() + 1);
+    let mut start = 0;
+    for part in &parts {
+        starts.push(start);
+        start += part.len() + 1;
+    }
+    starts.push(joined.len() + 1);
+    let segment = |start: usize, end: usize| &joined[starts[start]..starts[end] - 1];
+
+    // Going from the back, `ends[start]` is where the first route of
+    // the parts from `start` on ends, if they parse at all. Paths come
+    // from outside of the app, so every suffix is only tried once.
+    let len = parts.len();
+    let mut ends: Vec<Option<usize>> = vec![None; len + 1];
+    ends[len] = Some(len);
+    for start in (0..len).rev() {
+        ends[start] = (start + 1..=len)
+            .find(|&end| ends[end].is_some() && Route::from_segment(segment(start, end)).is_some());
+    }
+
+    let mut routes = vec![];
+    let mut start = 0;
+    while start < len {
+        let end = ends[start]?;
+        routes.push(Route::from_segment(segment(start, end))?);
+        start = end;
+    }
+    Some(routes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Route {
+        Home,
+        Threads,
+        Thread(String),
+        Profile(String),
+    }
+
+    impl RoutePath for Route {
+        fn to_segment(&self) -> String {
+            match self {
+                Route::Home => "home".to_owned(),
+                Route::Threads => "thread".to_owned(),
+                Route::Thread(id) => format!("thread/{id}"),
+                Route::Profile(id) => format!("profile/{id}"),
+            }
+        }
+
+        fn from_segment(segment: &str) -> Option<Self> {
+            match segment.split_once('/') {
+                None if segment == "home" => Some(Route::Home),
+                None if segment == "thread" => Some(Route::Threads),
+                Some(("thread", id)) if !id.contains('/') => Some(Route::Thread(id.to_owned())),
+                Some(("profile", id)) if !id.contains('/') => Some(Route::Profile(id.to_owned())),
+                _ => None,
+            }
+        }
+    }
+
+    fn round_trip(routes: Vec<Route>) {
+        let path = to_path(&routes);
+        assert_eq!(parse_path::<Route>(&path), Some(routes), "{path}");
+    }
+
+    #[test]
+    fn round_trips() {
+        round_trip(vec![Route::Home]);
+        round_trip(vec![Route::Home, Route::Threads]);
+        round_trip(vec![Route::Home, Route::Profile("xyz".to_owned())]);
+        round_trip(vec![
+            Route::Home,
+            Route::Thread("abc".to_owned()),
+            Route::Profile("xyz".to_owned()),
+        ]);
+        round_trip(vec![
+            Route::Threads,
+            Route::Thread("abc".to_owned()),
+            Route::Threads,
+        ]);
+    }
+
+    #[test]
+    fn backtracks_to_longer_segments() {
+        // `thread` alone is a route too, but `abc` isn't
+        assert_eq!(
+            parse_path::<Route>("/home/thread/abc"),
+            Some(vec![Route::Home, Route::Thread("abc".to_owned())])
+        );
+    }
+
+    #[test]
+    fn long_paths_that_fail_late() {
+        // every `thread/thread` pair parses two ways, which is a lot of
+        // ways to split the path before finding out the end is bad
+        let path = format!("{}/nope/nope", "/thread".repeat(400));
+        assert_eq!(parse_path::<Route>(&path), None);
+
+        let routes: Vec<Route> = (0..200)
+            .map(|i| Route::Thread(i.to_string()))
+            .chain([Route::Threads])
+            .collect();
+        round_trip(routes);
+    }
+
+    #[test]
+    fn rejects_bad_paths() {
+        assert_eq!(parse_path::<Route>(""), None);
+        assert_eq!(parse_path::<Route>("/"), None);
+        assert_eq!(parse_path::<Route>("/home/abc"), None);
+        assert_eq!(parse_path::<Route>("/profile"), None);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{render_bg, render_fg, Drag, NavAction, NavUiType, RouteResponse, State};
+use crate::{
+    a11y, animation::Motion, focus, nest, render_bg, render_fg, Animation, BackInput, Drag,
+    LayerTransition, NavAction, NavUiType, RouteResponse, Spring, State,
+};
 
 pub struct PopupSheet<'a, Route: Clone> {
     id_source: Option<egui::Id>,
@@ -7,15 +10,19 @@ pub struct PopupSheet<'a, Route: Clone> {
     split: Split,
     navigating: bool,
     returning: bool,
+    motion: Motion,
+    back_input: BackInput,
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Split {
     PercentFromTop(Percent),
     AbsoluteFromBottom(f32),
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Percent(u8); // 0–100
 
 impl Percent {
@@ -47,6 +54,8 @@ impl<'a, Route: Clone> PopupSheet<'a, Route> {
             navigating: false,
             returning: false,
             id_source: None,
+            motion: Motion::default(),
+            back_input: BackInput::empty(),
         }
     }
 
@@ -55,6 +64,26 @@ impl<'a, Route: Clone> PopupSheet<'a, Route> {
         self
     }
 
+    /// Use a timed animation with a fixed duration and easing for
+    /// opening and closing
+    pub fn animation(mut self, animation: Animation) -> Self {
+        self.motion = Motion::Timed(animation);
+        self
+    }
+
+    /// Use spring physics for opening and closing. This is the default.
+    pub fn spring(mut self, spring: Spring) -> Self {
+        self.motion = Motion::Spring(spring);
+        self
+    }
+
+    /// Keys and mouse buttons that close the sheet, usually
+    /// [`BackInput::Escape`]. Off by default.
+    pub fn back_input(mut self, back_input: BackInput) -> Self {
+        self.back_input = back_input;
+        self
+    }
+
     pub fn id_source(mut self, id: egui::Id) -> Self {
         self.id_source = Some(id);
         self
@@ -116,8 +145,7 @@ impl<'a, Route: Clone> PopupSheet<'a, Route> {
         };
         let mut state = State::load(ui.ctx(), id).unwrap_or(State {
             offset: max_height,
-            action: None,
-            popped_min_rect: None,
+            ..Default::default()
         });
 
         let avail_rect = ui.available_rect_before_wrap();
@@ -138,7 +166,11 @@ impl<'a, Route: Clone> PopupSheet<'a, Route> {
         if let Some(drag_action) = drag.handle(ui, Vec::new()) {
             let nav_action = match drag_action {
                 crate::drag::DragAction::Dragging => NavAction::Dragging,
-                crate::drag::DragAction::DragReleased { threshold_met } => {
+                crate::drag::DragAction::DragReleased {
+                    threshold_met,
+                    velocity,
+                } => {
+                    state.velocity = velocity;
                     if threshold_met {
                         NavAction::Returning(crate::ReturnType::Drag)
                     } else {
@@ -155,7 +187,9 @@ impl<'a, Route: Clone> PopupSheet<'a, Route> {
                 state.offset = content_rect.bottom();
                 state.action = Some(NavAction::Navigating);
             }
-        } else if self.returning && !matches!(state.action, Some(NavAction::Returning(_))) {
+        } else if (self.returning || nest::take_return(ui.ctx(), id))
+            && !matches!(state.action, Some(NavAction::Returning(_)))
+        {
             state.offset = max_height;
             state.action = Some(NavAction::Returning(crate::ReturnType::Click));
         }
@@ -168,7 +202,7 @@ impl<'a, Route: Clone> PopupSheet<'a, Route> {
                 crate::DragDirection::Vertical,
                 max_height,
                 max_size,
-                true,
+                Some(self.motion),
             );
         }
 
@@ -177,7 +211,11 @@ impl<'a, Route: Clone> PopupSheet<'a, Route> {
             (t * 255.0).round() as u8
         };
 
-        let bg_resp = render_bg(ui, None, bg_rect, bg_rect, Some(alpha), |ui| {
+        let transition = LayerTransition {
+            scrim: egui::Color32::from_black_alpha(alpha),
+            ..LayerTransition::IDENTITY
+        };
+        let bg_resp = render_bg(ui, ui.id(), transition, bg_rect, bg_rect, |ui| {
             show_route(ui, NavUiType::Title, self.bg_route);
             show_route(ui, NavUiType::Body, self.bg_route);
             Vec::new()
@@ -190,29 +228,47 @@ impl<'a, Route: Clone> PopupSheet<'a, Route> {
             state.action = Some(NavAction::Returning(crate::ReturnType::Click));
         }
 
-        state.store(ui.ctx(), id);
+        let fg_layer = egui::LayerId::new(egui::Order::Foreground, id.with("fg"));
+        // navs in the sheet pass returns to it, not to what is behind it
+        let (response, _) = nest::scope(&ui.ctx().clone(), id, || {
+            render_fg(
+                ui,
+                id.with("fg"),
+                fg_layer,
+                LayerTransition::IDENTITY,
+                content_rect,
+                content_rect,
+                |ui| {
+                    let ctx = ui.ctx().clone();
+                    let r = a11y::node(&ctx, id.with("dialog"), a11y::Role::Dialog, None, || {
+                        focus::anchor(ui, id.with("focus_anchor"));
+                        if matches!(state.action, Some(NavAction::Returned(..))) {
+                            show_route(ui, NavUiType::Body, self.bg_route)
+                        } else {
+                            show_route(ui, NavUiType::Body, self.fg_route)
+                        }
+                    });
 
-        let response = render_fg(
-            ui,
-            id.with("fg"),
-            egui::LayerId::new(egui::Order::Foreground, id.with("fg")),
-            None,
-            content_rect,
-            content_rect,
-            |ui| {
-                let r = if matches!(state.action, Some(NavAction::Returned(_))) {
-                    show_route(ui, NavUiType::Body, self.bg_route)
-                } else {
-                    show_route(ui, NavUiType::Body, self.fg_route)
-                };
-
-                RouteResponse {
-                    response: r,
-                    can_take_drag_from: Vec::new(),
-                }
-            },
-        )
-        .response;
+                    RouteResponse {
+                        response: r,
+                        can_take_drag_from: Vec::new(),
+                    }
+                },
+            )
+            .response
+        });
+
+        if !matches!(state.action, Some(NavAction::Returned(..))) {
+            focus::trap(ui.ctx(), fg_layer, id.with("focus_anchor"));
+        }
+
+        if self.back_input.pressed(ui.ctx())
+            && !matches!(state.action, Some(NavAction::Returning(_)))
+        {
+            state.action = Some(NavAction::Returning(crate::ReturnType::Key));
+        }
+
+        state.store(ui.ctx(), id);
 
         PopupResponse {
             response,
@@ -0,0 +1,341 @@
+use egui::UiBuilder;
+
+use crate::{
+    entry::Entries, lifecycle::Phase, nest, render_fg, LayerTransition, LayoutDirection, Nav,
+    NavAction, NavResponse, NavUiType, ReturnGuard, ReturnType, RouteResponse,
+};
+
+/// A master-detail view. When the ui is wider than the breakpoint, the
+/// root route stays in a pane of its own and the routes above it are
+/// shown next to it. Narrower uis get a regular [`Nav`].
+///
+/// Both layouts share the same stack and widget state, so the ui can be
+/// resized across the breakpoint at any time.
+pub struct SplitNav<'a, Route: Clone> {
+    nav: Nav<'a, Route>,
+    breakpoint: f32,
+    master_width: f32,
+}
+
+pub struct SplitResponse<R> {
+    /// The response of the nav. When split, this is the detail pane,
+    /// which is only shown once there is a route above the root.
+    pub nav: Option<NavResponse<R>>,
+    /// The root route's title and body, when split
+    pub master_title_response: Option<R>,
+    pub master_response: Option<R>,
+    pub action: Option<NavAction>,
+}
+
+impl<'a, Route: Clone> SplitNav<'a, Route> {
+    pub fn new(nav: Nav<'a, Route>) -> Self {
+        SplitNav {
+            nav,
+            breakpoint: 600.0,
+            master_width: 300.0,
+        }
+    }
+
+    /// The width above which the master pane is shown. Defaults to 600.
+    pub fn breakpoint(mut self, width: f32) -> Self {
+        self.breakpoint = width;
+        self
+    }
+
+    /// The width of the master pane. Defaults to 300.
+    pub fn master_width(mut self, width: f32) -> Self {
+        self.master_width = width;
+        self
+    }
+
+    /// Is `ui` wide enough to show both panes?
+    pub fn is_split(&self, ui: &egui::Ui) -> bool {
+        ui.available_width() > self.breakpoint
+    }
+
+    pub fn show<F, R>(&self, ui: &mut egui::Ui, show_route: F) -> SplitResponse<R>
+    where
+        F: Fn(&mut egui::Ui, NavUiType, &Nav<Route>) -> RouteResponse<R>,
+    {
+        let mut show_route = show_route;
+
+        self.show_internal(ui, &mut show_route)
+    }
+
+    pub fn show_mut<F, R>(&self, ui: &mut egui::Ui, mut show_route: F) -> SplitResponse<R>
+    where
+        F: FnMut(&mut egui::Ui, NavUiType, &Nav<Route>) -> RouteResponse<R>,
+    {
+        self.show_internal(ui, &mut show_route)
+    }
+
+    fn show_internal<F, R>(&self, ui: &mut egui::Ui, show_route: &mut F) -> SplitResponse<R>
+    where
+        F: FnMut(&mut egui::Ui, NavUiType, &Nav<Route>) -> RouteResponse<R>,
+    {
+        let nav = &self.nav;
+        if !self.is_split(ui) {
+            let response = nav.show_internal(ui, show_route);
+            return SplitResponse {
+                action: response.action,
+                nav: Some(response),
+                master_title_response: None,
+                master_response: None,
+            };
+        }
+
+        let id = nav.id(ui);
+        let rect = ui.available_rect_before_wrap();
+        let width = self.master_width.min(rect.width());
+        let direction = nav
+            .layout_direction
+            .unwrap_or_else(|| LayoutDirection::from_ui(ui));
+        let (master_rect, detail_rect) = match direction {
+            LayoutDirection::LeftToRight => rect.split_left_right_at_x(rect.left() + width),
+            LayoutDirection::RightToLeft => {
+                let (detail_rect, master_rect) = rect.split_left_right_at_x(rect.right() - width);
+                (master_rect, detail_rect)
+            }
+        };
+
+        // the root keeps the id scope it has in the narrow layout
+        let mut entries = Entries::load(ui.ctx(), id.with("entries"));
+        entries.sync(nav.route.len(), nav.forward.is_some());
+        let master_entry = nav.entry_scope(&mut entries, id, nav.route, 0);
+        entries.store(ui.ctx(), id.with("entries"));
+
+        let master_nav = Nav {
+            route: &nav.route[..1],
+            navigating: false,
+            replacing: false,
+            returning: false,
+            forwarding: false,
+            forward: None,
+            entry_id: Some(master_entry),
+            ..*nav
+        };
+        let layer_id = ui.layer_id();
+        let (master_title_response, master_response) = render_fg(
+            ui,
+            master_entry,
+            layer_id,
+            LayerTransition::IDENTITY,
+            master_rect,
+            master_rect,
+            |ui| {
+                let title = show_route(ui, NavUiType::Title, &master_nav).response;
+                let body = show_route(ui, NavUiType::Body, &master_nav).response;
+                RouteResponse {
+                    response: (title, body),
+                    can_take_drag_from: Vec::new(),
+                }
+            },
+        )
+        .response;
+
+        let separator = match direction {
+            LayoutDirection::LeftToRight => master_rect.right(),
+            LayoutDirection::RightToLeft => master_rect.left(),
+        };
+        ui.painter().vline(
+            separator,
+            rect.y_range(),
+            ui.visuals().widgets.noninteractive.bg_stroke,
+        );
+
+        // Transitions between the root and the first detail route have
+        // nothing to animate, the detail pane just changes
+        let len = nav.route.len();
+        // the detail nav is at its root then, but we aren't
+        let at_detail_root = len == 2 && !nav.returning;
+        let settled = if nav.returning && len == 1 {
+            nest::pass_return(ui.ctx()).then_some(NavAction::ReturnDelegated)
+        } else if nav.returning && nav.return_levels + 1 >= len {
+            Some(match nav.top_guard() {
+                ReturnGuard::Allow => NavAction::Returned(ReturnType::Click, len - 1),
+                guard => NavAction::ReturnBlocked(ReturnType::Click, guard),
+            })
+        } else if at_detail_root && nest::take_return(ui.ctx(), id) {
+            Some(root_return(nav, ReturnType::Click))
+        } else if nav.navigating && len == 2 {
+            Some(NavAction::Navigated)
+        } else if nav.replacing && len == 2 {
+            Some(NavAction::Replaced)
+        } else if nav.forwarding && len == 1 && nav.forward.is_some() {
+            Some(NavAction::Forwarded)
+        } else {
+            None
+        };
+
+        let response = if len > 1 {
+            // the detail nav is the same nav, minus the root
+            let mut detail_nav = Nav {
+                route: &nav.route[1..],
+                below: &nav.route[..1],
+                fixed_id: Some(id),
+                ..*nav
+            };
+            if settled.is_some() {
+                detail_nav.navigating = false;
+                detail_nav.replacing = false;
+                detail_nav.returning = false;
+                detail_nav.forwarding = false;
+            }
+
+            let mut detail_ui = ui.new_child(UiBuilder::new().max_rect(detail_rect));
+            Some(detail_nav.show_internal(&mut detail_ui, show_route))
+        } else {
+            if let Some(lifecycle) = nav.lifecycle {
+                let visible = vec![(master_entry, &nav.route[0], Phase::Appeared)];
+                lifecycle.update(ui.ctx(), id.with("lifecycle"), visible);
+            }
+            None
+        };
+
+        let action = settled.or(response.as_ref().and_then(|response| response.action));
+
+        // checked after the detail pane so navs nested in it go back first
+        let action = if at_detail_root
+            && settled.is_none()
+            && !matches!(
+                action,
+                Some(NavAction::Returning(_))
+                    | Some(NavAction::Dragging)
+                    | Some(NavAction::Forwarding)
+            )
+            && nav.back_input.pressed(ui.ctx())
+        {
+            Some(root_return(nav, ReturnType::Key))
+        } else {
+            action
+        };
+
+        SplitResponse {
+            action,
+            nav: response,
+            master_title_response: Some(master_title_response),
+            master_response: Some(master_response),
+        }
+    }
+}
+
+/// Return from the first detail route to the root, unless it refuses
+fn root_return<Route: Clone>(nav: &Nav<Route>, typ: ReturnType) -> NavAction {
+    match nav.top_guard() {
+        ReturnGuard::Allow => NavAction::Returned(typ, 1),
+        guard => NavAction::ReturnBlocked(typ, guard),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+    use crate::{tests::Frames, BackInput, NavStack, RouteLifecycle};
+
+    fn run(
+        ctx: &egui::Context,
+        events: Vec<egui::Event>,
+        split: &SplitNav<&'static str>,
+    ) -> Option<NavAction> {
+        let input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::Pos2::ZERO,
+                egui::vec2(1000.0, 800.0),
+            )),
+            events,
+            ..Default::default()
+        };
+        let mut action = None;
+        let _ = ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                action = split
+                    .show(ui, |_ui, _typ, _nav| RouteResponse {
+                        response: (),
+                        can_take_drag_from: vec![],
+                    })
+                    .action;
+            });
+        });
+        action
+    }
+
+    #[test]
+    fn back_at_the_first_detail_route_pops_to_the_root() {
+        let ctx = egui::Context::default();
+        let routes = ["a", "b"];
+        let split = SplitNav::new(Nav::new(&routes).back_input(BackInput::Escape));
+        run(&ctx, vec![], &split);
+
+        let escape = egui::Event::Key {
+            key: egui::Key::Escape,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: egui::Modifiers::NONE,
+        };
+        assert_eq!(
+            run(&ctx, vec![escape], &split),
+            Some(NavAction::Returned(ReturnType::Key, 1))
+        );
+    }
+
+    #[test]
+    fn back_input_goes_to_a_nav_nested_in_the_detail_pane() {
+        let mut frames = Frames::new();
+        let routes = ["a", "b"];
+        let split = SplitNav::new(Nav::new(&routes).back_input(BackInput::Escape));
+        let mut inner = NavStack::with_routes(vec!["x", "y"])
+            .id_source(egui::Id::new("inner"))
+            .back_input(BackInput::Escape);
+        let mut actions = vec![];
+        {
+            let mut show = |ui: &mut egui::Ui| {
+                let response = split.show_mut(ui, |ui, typ, nav| {
+                    if matches!(typ, NavUiType::Body) && *nav.top() == "b" {
+                        inner.show(ui, |_ui, _typ, _nav| RouteResponse {
+                            response: (),
+                            can_take_drag_from: vec![],
+                        });
+                    }
+                    RouteResponse {
+                        response: (),
+                        can_take_drag_from: vec![],
+                    }
+                });
+                actions.extend(response.action);
+            };
+
+            frames.run(2, &mut show);
+            frames.press(egui::Key::Escape, &mut show);
+            frames.run(120, &mut show);
+        }
+
+        assert_eq!(inner.routes(), ["x"]);
+        assert!(!actions
+            .iter()
+            .any(|action| matches!(action, NavAction::Returned(..))));
+    }
+
+    #[derive(Default)]
+    struct Disappeared(RefCell<Vec<&'static str>>);
+
+    impl RouteLifecycle<&'static str> for Disappeared {
+        fn will_disappear(&self, route: &&'static str, _entry: egui::Id) {
+            self.0.borrow_mut().push(route);
+        }
+    }
+
+    #[test]
+    fn the_master_route_stays_on_screen() {
+        let ctx = egui::Context::default();
+        let hooks = Disappeared::default();
+        for routes in [&["a"][..], &["a", "b"], &["a", "b", "c"], &["a"]] {
+            let split = SplitNav::new(Nav::new(routes).lifecycle(&hooks));
+            run(&ctx, vec![], &split);
+        }
+
+        assert_eq!(*hooks.0.borrow(), ["b", "c"]);
+    }
+}
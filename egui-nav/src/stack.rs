@@ -0,0 +1,1102 @@
+use crate::{
+    animation::Motion, lifecycle::OwnedLifecycle, Animation, Axis, BackInput, DragAngle,
+    DragThreshold, HistoryEvent, LayoutDirection, Nav, NavAction, NavHistory, NavResponse,
+    NavTransition, NavUiType, ReturnGuard, RouteLifecycle, RouteResponse, Spring,
+};
+
+type RouteLabel<Route> = Box<dyn Fn(&Route) -> String>;
+type InitialFocus<Route> = Box<dyn Fn(&Route) -> Option<egui::Id>>;
+type RouteKey<Route> = Box<dyn Fn(&Route) -> egui::Id>;
+type ReturnGuardFn<Route> = Box<dyn Fn(&Route) -> ReturnGuard>;
+
+/// An owned route stack that drives a [`Nav`].
+///
+/// Instead of keeping a `Vec<Route>` and the `navigating`/`returning` flags
+/// in your app and fixing them up when a transition finishes, call the
+/// stack operations and let [`NavStack::show`] apply the mutation once
+/// the animation is done.
+pub struct NavStack<Route: Clone> {
+    routes: Vec<Route>,
+    /// routes we returned from, the next one to go forward to is last
+    forward: Vec<Route>,
+    forward_history: bool,
+    navigating: bool,
+    replacing: bool,
+    returning: bool,
+    forwarding: bool,
+    return_levels: usize,
+    id_source: Option<egui::Id>,
+    animate_transitions: bool,
+    back_input: BackInput,
+    motion: Option<Motion>,
+    transition: Option<Box<dyn NavTransition>>,
+    replace_transition: Option<Box<dyn NavTransition>>,
+    swipe_edge: Option<f32>,
+    return_threshold: Option<DragThreshold>,
+    drag_angle: Option<DragAngle>,
+    layout_direction: Option<LayoutDirection>,
+    axis: Option<Axis>,
+    route_label: Option<RouteLabel<Route>>,
+    initial_focus: Option<InitialFocus<Route>>,
+    route_key: Option<RouteKey<Route>>,
+    return_guard: Option<ReturnGuardFn<Route>>,
+    /// the return in flight skips the guard
+    confirmed: bool,
+    history: Option<Box<dyn NavHistory<Route>>>,
+    /// back events of the history the return in flight answers
+    history_backs: usize,
+    lifecycle: Option<OwnedLifecycle<Route>>,
+    /// the top route was swapped in place since the last frame
+    top_swapped: bool,
+}
+
+/// What [`NavStack::persist`] saves
+#[cfg(feature = "serde")]
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct Saved<Route> {
+    routes: Vec<Route>,
+    forward: Vec<Route>,
+}
+
+impl<Route: Clone> NavStack<Route> {
+    pub fn new(root: Route) -> Self {
+        Self::with_routes(vec![root])
+    }
+
+    pub fn with_routes(routes: Vec<Route>) -> Self {
+        // same precondition as Nav
+        assert!(!routes.is_empty(), "NavStack routes cannot be empty");
+
+        NavStack {
+            routes,
+            forward: Vec::new(),
+            forward_history: false,
+            navigating: false,
+            replacing: false,
+            returning: false,
+            forwarding: false,
+            return_levels: 1,
+            id_source: None,
+            animate_transitions: true,
+            back_input: BackInput::empty(),
+            motion: None,
+            transition: None,
+            replace_transition: None,
+            swipe_edge: None,
+            return_threshold: None,
+            drag_angle: None,
+            layout_direction: None,
+            axis: None,
+            route_label: None,
+            initial_focus: None,
+            route_key: None,
+            return_guard: None,
+            confirmed: false,
+            history: None,
+            history_backs: 0,
+            lifecycle: None,
+            top_swapped: false,
+        }
+    }
+
+    pub fn id_source(mut self, id: egui::Id) -> Self {
+        self.id_source = Some(id);
+        self
+    }
+
+    pub fn animate_transitions(mut self, animate: bool) -> Self {
+        self.animate_transitions = animate;
+        self
+    }
+
+    pub fn back_input(mut self, back_input: BackInput) -> Self {
+        self.back_input = back_input;
+        self
+    }
+
+    pub fn animation(mut self, animation: Animation) -> Self {
+        self.motion = Some(Motion::Timed(animation));
+        self
+    }
+
+    pub fn spring(mut self, spring: Spring) -> Self {
+        self.motion = Some(Motion::Spring(spring));
+        self
+    }
+
+    pub fn transition(mut self, transition: impl NavTransition + 'static) -> Self {
+        self.transition = Some(Box::new(transition));
+        self
+    }
+
+    /// See [`Nav::replace_transition`]
+    pub fn replace_transition(mut self, transition: impl NavTransition + 'static) -> Self {
+        self.replace_transition = Some(Box::new(transition));
+        self
+    }
+
+    /// See [`Nav::swipe_edge`]
+    pub fn swipe_edge(mut self, width: f32) -> Self {
+        self.swipe_edge = Some(width);
+        self
+    }
+
+    /// See [`Nav::return_threshold`]
+    pub fn return_threshold(mut self, threshold: DragThreshold) -> Self {
+        self.return_threshold = Some(threshold);
+        self
+    }
+
+    /// See [`Nav::drag_angle`]
+    pub fn drag_angle(mut self, angle: DragAngle) -> Self {
+        self.drag_angle = Some(angle);
+        self
+    }
+
+    /// See [`Nav::layout_direction`]
+    pub fn layout_direction(mut self, direction: LayoutDirection) -> Self {
+        self.layout_direction = Some(direction);
+        self
+    }
+
+    /// See [`Nav::axis`]
+    pub fn axis(mut self, axis: Axis) -> Self {
+        self.axis = Some(axis);
+        self
+    }
+
+    /// Remember routes we return from so they can be swiped or
+    /// [`NavStack::go_forward`]ed back to
+    pub fn forward_history(mut self, enabled: bool) -> Self {
+        self.forward_history = enabled;
+        self
+    }
+
+    /// See [`Nav::route_label`]
+    pub fn route_label(mut self, label: impl Fn(&Route) -> String + 'static) -> Self {
+        self.route_label = Some(Box::new(label));
+        self
+    }
+
+    /// See [`Nav::initial_focus`]
+    pub fn initial_focus(mut self, focus: impl Fn(&Route) -> Option<egui::Id> + 'static) -> Self {
+        self.initial_focus = Some(Box::new(focus));
+        self
+    }
+
+    /// See [`Nav::route_key`]
+    pub fn route_key(mut self, key: impl Fn(&Route) -> egui::Id + 'static) -> Self {
+        self.route_key = Some(Box::new(key));
+        self
+    }
+
+    /// See [`Nav::return_guard`]. A blocked return is dropped, use
+    /// [`NavStack::pop_confirmed`] once the user confirmed.
+    pub fn return_guard(mut self, guard: impl Fn(&Route) -> ReturnGuard + 'static) -> Self {
+        self.return_guard = Some(Box::new(guard));
+        self
+    }
+
+    /// Report pushes and pops to `history`, and follow its back and
+    /// forward events, see [`NavStack::poll_history`]
+    pub fn history(mut self, history: impl NavHistory<Route> + 'static) -> Self {
+        self.history = Some(Box::new(history));
+        self
+    }
+
+    /// See [`Nav::lifecycle`]
+    pub fn lifecycle(mut self, lifecycle: impl RouteLifecycle<Route> + 'static) -> Self
+    where
+        Route: Send + Sync + 'static,
+    {
+        self.lifecycle = Some(OwnedLifecycle::new(lifecycle));
+        self
+    }
+
+    pub fn routes(&self) -> &[Route] {
+        &self.routes
+    }
+
+    /// NavStack guarantees there is at least one route element
+    pub fn top(&self) -> &Route {
+        &self.routes[self.routes.len() - 1]
+    }
+
+    /// Are we in the middle of a push, pop or replace?
+    pub fn is_transitioning(&self) -> bool {
+        self.navigating || self.replacing || self.returning || self.forwarding
+    }
+
+    /// Push a route and animate to it. A pop that is still animating is
+    /// finished first, and the new route slides in from where it was.
+    pub fn push(&mut self, route: Route) {
+        if self.returning {
+            let len = self.routes.len().saturating_sub(self.return_levels).max(1);
+            self.truncate(len);
+            self.returning = false;
+        }
+
+        self.forward.clear();
+        self.forwarding = false;
+        // pushes are reported right away, a push that is interrupted
+        // by a pop is reported as popped again
+        if let Some(history) = &mut self.history {
+            history.push(&route);
+        }
+        self.routes.push(route);
+        self.navigating = true;
+    }
+
+    /// Animate back to the route we last returned from. Returns false if
+    /// there is no forward history.
+    pub fn go_forward(&mut self) -> bool {
+        if self.is_transitioning() || self.forward.is_empty() {
+            return false;
+        }
+
+        self.forwarding = true;
+        true
+    }
+
+    /// The route [`NavStack::go_forward`] would bring back
+    pub fn forward_route(&self) -> Option<&Route> {
+        self.forward.last()
+    }
+
+    /// Animate back to the previous route. The top route is removed once
+    /// the transition finishes. Popping while a push is animating reverses
+    /// it, and popping again during a pop goes back one more level.
+    /// Returns false if there was nothing to pop.
+    pub fn pop(&mut self) -> bool {
+        // the route being replaced is already on its way out
+        if self.replacing && self.routes.len() > 1 {
+            self.replaced();
+            self.replacing = false;
+        }
+        // and the forward route isn't on the stack yet
+        self.forwarding = false;
+
+        let levels = if self.returning {
+            self.return_levels + 1
+        } else {
+            1
+        };
+        if levels >= self.routes.len() {
+            return false;
+        }
+
+        self.pop_to_index(self.routes.len() - levels - 1)
+    }
+
+    /// Swap the top route without growing the stack. The old top is shown
+    /// behind the new one until the transition finishes, then removed.
+    /// During a pop, the route we're leaving is the one replaced, and
+    /// during a push the route that is sliding in.
+    pub fn replace(&mut self, route: Route) {
+        if self.returning {
+            self.push(route);
+            return;
+        }
+
+        if self.navigating {
+            if let Some(history) = &mut self.history {
+                history.replace(&route);
+            }
+            let top = self.routes.len() - 1;
+            self.routes[top] = route;
+            self.top_swapped = true;
+            return;
+        }
+
+        self.forward.clear();
+        self.forwarding = false;
+        self.routes.push(route);
+        self.replacing = true;
+    }
+
+    /// Pop without asking the [`NavStack::return_guard`], for when the
+    /// user confirmed leaving a route that answered
+    /// [`ReturnGuard::Confirm`]
+    pub fn pop_confirmed(&mut self) -> bool {
+        self.confirmed = self.pop();
+        self.confirmed
+    }
+
+    /// Animate back to the root route
+    pub fn pop_to_root(&mut self) -> bool {
+        self.pop_to_index(0)
+    }
+
+    /// Animate back to the topmost route matching `pred`. Returns false if
+    /// no route below the top matches.
+    pub fn pop_until(&mut self, pred: impl Fn(&Route) -> bool) -> bool {
+        let below_top = &self.routes[..self.routes.len() - 1];
+        let Some(index) = below_top.iter().rposition(pred) else {
+            return false;
+        };
+
+        self.pop_to_index(index)
+    }
+
+    /// Animate to `routes`, like following a deep link. Going back to
+    /// part of the current stack pops, a longer stack pushes the extra
+    /// routes with only the last one sliding in, and anything else
+    /// replaces the top. Returns false if `routes` is empty or already
+    /// shown.
+    pub fn navigate_to(&mut self, mut routes: Vec<Route>) -> bool
+    where
+        Route: PartialEq,
+    {
+        if routes.is_empty() {
+            return false;
+        }
+
+        self.settle();
+        if routes == self.routes {
+            return false;
+        }
+
+        let common = self
+            .routes
+            .iter()
+            .zip(&routes)
+            .take_while(|(current, target)| current == target)
+            .count();
+        if common == routes.len() {
+            return self.pop_to_index(common - 1);
+        }
+
+        let Some(last) = routes.pop() else {
+            return false;
+        };
+        if common == self.routes.len() {
+            if let Some(history) = &mut self.history {
+                routes[common..]
+                    .iter()
+                    .for_each(|route| history.push(route));
+            }
+            self.routes = routes;
+            self.push(last);
+        } else {
+            // the current top stays visible until the new one covers it
+            let top = self.routes.pop();
+            if let Some(history) = &mut self.history {
+                history.pop(self.routes.len() + 1 - common);
+                routes[common..]
+                    .iter()
+                    .chain(&top)
+                    .for_each(|route| history.push(route));
+            }
+            self.routes = routes.into_iter().chain(top).collect();
+            self.replace(last);
+        }
+        true
+    }
+
+    /// Pop down to `len` routes right away
+    fn truncate(&mut self, len: usize) {
+        let levels = self.routes.len().saturating_sub(len);
+        if levels == 0 {
+            return;
+        }
+
+        self.routes.truncate(len);
+        if let Some(history) = &mut self.history {
+            history.pop(levels);
+        }
+    }
+
+    /// Finish the transition that is animating right away
+    fn settle(&mut self) {
+        if self.returning {
+            let len = self.routes.len().saturating_sub(self.return_levels).max(1);
+            self.truncate(len);
+        }
+
+        if self.replacing && self.routes.len() > 1 {
+            self.replaced();
+        }
+
+        if self.forwarding {
+            self.forwarded();
+        }
+
+        self.navigating = false;
+        self.replacing = false;
+        self.returning = false;
+        self.forwarding = false;
+    }
+
+    fn pop_to_index(&mut self, index: usize) -> bool {
+        let len = self.routes.len();
+        if index + 1 >= len {
+            return false;
+        }
+
+        self.returning = true;
+        self.return_levels = len - 1 - index;
+        true
+    }
+
+    /// The [`Nav`] for the current stack
+    pub fn nav(&self) -> Nav<'_, Route> {
+        let mut nav = Nav::new(&self.routes)
+            .navigating(self.navigating)
+            .replacing(self.replacing)
+            .returning(self.returning)
+            .forwarding(self.forwarding)
+            .return_levels(self.return_levels)
+            .animate_transitions(self.animate_transitions)
+            .back_input(self.back_input)
+            .forward(self.forward.last());
+
+        if let Some(id) = self.id_source {
+            nav = nav.id_source(id);
+        }
+
+        match self.motion {
+            Some(Motion::Timed(animation)) => nav = nav.animation(animation),
+            Some(Motion::Spring(spring)) => nav = nav.spring(spring),
+            None => {}
+        }
+
+        if let Some(transition) = &self.transition {
+            nav = nav.transition(transition.as_ref());
+        }
+
+        if let Some(transition) = &self.replace_transition {
+            nav = nav.replace_transition(transition.as_ref());
+        }
+
+        if let Some(width) = self.swipe_edge {
+            nav = nav.swipe_edge(width);
+        }
+
+        if let Some(threshold) = self.return_threshold {
+            nav = nav.return_threshold(threshold);
+        }
+
+        if let Some(angle) = self.drag_angle {
+            nav = nav.drag_angle(angle);
+        }
+
+        if let Some(direction) = self.layout_direction {
+            nav = nav.layout_direction(direction);
+        }
+
+        if let Some(axis) = self.axis {
+            nav = nav.axis(axis);
+        }
+
+        if let Some(label) = &self.route_label {
+            nav = nav.route_label(label.as_ref());
+        }
+
+        if let Some(focus) = &self.initial_focus {
+            nav = nav.initial_focus(focus.as_ref());
+        }
+
+        if let Some(key) = &self.route_key {
+            nav = nav.route_key(key.as_ref());
+        }
+
+        if let Some(guard) = self.return_guard.as_ref().filter(|_| !self.confirmed) {
+            nav = nav.return_guard(guard.as_ref());
+        }
+
+        if let Some(lifecycle) = &self.lifecycle {
+            nav.lifecycle = Some(lifecycle.get());
+        }
+        nav.top_swapped = self.top_swapped;
+
+        nav
+    }
+
+    /// Apply a [`NavAction`] returned from a [`Nav`] built with
+    /// [`NavStack::nav`]. [`NavStack::show`] does this for you.
+    pub fn handle_action(&mut self, action: Option<NavAction>) {
+        // the nav has seen the swap by now
+        self.top_swapped = false;
+        match action {
+            Some(NavAction::Returned(_, levels)) => {
+                let len = self.routes.len().saturating_sub(levels).max(1);
+                let popped = self.routes.split_off(len);
+                if let Some(history) = &mut self.history {
+                    if !popped.is_empty() {
+                        history.pop(popped.len());
+                    }
+                }
+                if self.forward_history {
+                    self.forward.extend(popped.into_iter().rev());
+                }
+                self.returning = false;
+                self.navigating = false;
+            }
+            Some(NavAction::Navigated) => {
+                self.navigating = false;
+            }
+            Some(NavAction::Forwarded) => {
+                self.forwarded();
+                self.forwarding = false;
+            }
+            Some(NavAction::Replaced) => {
+                if self.routes.len() > 1 {
+                    self.replaced();
+                }
+                self.replacing = false;
+            }
+            Some(NavAction::ReturnBlocked(..)) => {
+                if let Some(history) = &mut self.history {
+                    (0..self.history_backs).for_each(|_| history.blocked());
+                }
+                self.returning = false;
+            }
+            Some(NavAction::ReturnDelegated) => {
+                self.returning = false;
+            }
+            _ => {}
+        }
+
+        if !self.returning {
+            self.confirmed = false;
+            self.history_backs = 0;
+        }
+    }
+
+    /// Push the forward route back on the stack
+    fn forwarded(&mut self) {
+        if let Some(route) = self.forward.pop() {
+            self.routes.push(route);
+            if let Some(history) = &mut self.history {
+                history.forward();
+            }
+        }
+    }
+
+    /// Remove the route below the top, once a replace is done
+    fn replaced(&mut self) {
+        let len = self.routes.len();
+        self.routes.remove(len - 2);
+        if let Some(history) = &mut self.history {
+            history.replace(&self.routes[len - 2]);
+        }
+    }
+
+    /// Go back or forward for the events of the history. Going forward
+    /// needs [`NavStack::forward_history`]. [`NavStack::show`] does this
+    /// for you.
+    pub fn poll_history(&mut self) {
+        let Some(history) = &mut self.history else {
+            return;
+        };
+
+        for event in history.poll() {
+            match event {
+                HistoryEvent::Back => {
+                    if self.pop() {
+                        self.history_backs += 1;
+                    } else if let Some(history) = &mut self.history {
+                        history.blocked();
+                    }
+                }
+                HistoryEvent::Forward => {
+                    self.go_forward();
+                }
+            }
+        }
+    }
+
+    pub fn show<F, R>(&mut self, ui: &mut egui::Ui, show_route: F) -> NavResponse<R>
+    where
+        F: Fn(&mut egui::Ui, NavUiType, &Nav<Route>) -> RouteResponse<R>,
+    {
+        self.poll_history();
+        let response = self.nav().show(ui, show_route);
+        self.handle_action(response.action);
+        response
+    }
+
+    pub fn show_mut<F, R>(&mut self, ui: &mut egui::Ui, show_route: F) -> NavResponse<R>
+    where
+        F: FnMut(&mut egui::Ui, NavUiType, &Nav<Route>) -> RouteResponse<R>,
+    {
+        self.poll_history();
+        let response = self.nav().show_mut(ui, show_route);
+        self.handle_action(response.action);
+        response
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Route> NavStack<Route>
+where
+    Route: Clone + serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+{
+    /// Save the routes in egui's persisted memory under `id`, so
+    /// [`NavStack::restore`] can bring them back after a restart. Call it
+    /// whenever the stack changes, or every frame.
+    pub fn persist(&self, ctx: &egui::Context, id: egui::Id) {
+        let saved = Saved {
+            routes: self.routes.clone(),
+            forward: self.forward.clone(),
+        };
+        ctx.data_mut(|d| d.insert_persisted(id, saved));
+    }
+
+    /// Put back the routes saved with [`NavStack::persist`]. The [`Nav`]
+    /// restores its own transition state, so pass the same `id_source`
+    /// as before. Returns false when nothing was saved.
+    pub fn restore(&mut self, ctx: &egui::Context, id: egui::Id) -> bool {
+        let Some(saved) = ctx.data_mut(|d| d.get_persisted::<Saved<Route>>(id)) else {
+            return false;
+        };
+        if saved.routes.is_empty() {
+            return false;
+        }
+
+        self.routes = saved.routes;
+        self.forward = saved.forward;
+        self.navigating = false;
+        self.replacing = false;
+        self.returning = false;
+        self.forwarding = false;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::Frames, MemoryHistory, ReturnType};
+
+    fn show(frames: &mut Frames, stack: &mut NavStack<&'static str>, n: usize) -> Vec<NavAction> {
+        let mut actions = vec![];
+        frames.run(n, |ui| {
+            let response = stack.show(ui, |_ui, _typ, _nav| RouteResponse {
+                response: (),
+                can_take_drag_from: vec![],
+            });
+            actions.extend(response.action);
+        });
+        actions
+    }
+
+    #[test]
+    fn pop_to_root_returns_in_one_transition() {
+        let mut frames = Frames::new();
+        let mut stack = NavStack::with_routes(vec!["a", "b", "c"]);
+        show(&mut frames, &mut stack, 2);
+
+        stack.pop_to_root();
+        let actions = show(&mut frames, &mut stack, 120);
+
+        let returned: Vec<_> = actions
+            .iter()
+            .filter(|action| matches!(action, NavAction::Returned(..)))
+            .collect();
+        assert_eq!(returned, [&NavAction::Returned(ReturnType::Click, 2)]);
+        assert_eq!(stack.routes(), ["a"]);
+    }
+
+    #[test]
+    fn go_forward_after_returning() {
+        let mut frames = Frames::new();
+        let mut stack = NavStack::with_routes(vec!["a", "b"]).forward_history(true);
+        show(&mut frames, &mut stack, 2);
+
+        assert!(!stack.go_forward());
+        stack.pop();
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a"]);
+        assert_eq!(stack.forward_route(), Some(&"b"));
+
+        assert!(stack.go_forward());
+        let actions = show(&mut frames, &mut stack, 120);
+        assert!(actions.contains(&NavAction::Forwarded));
+        assert_eq!(stack.routes(), ["a", "b"]);
+        assert_eq!(stack.forward_route(), None);
+    }
+
+    #[test]
+    fn pushing_drops_forward_history() {
+        let mut frames = Frames::new();
+        let mut stack = NavStack::with_routes(vec!["a", "b"]).forward_history(true);
+        show(&mut frames, &mut stack, 2);
+
+        stack.pop();
+        show(&mut frames, &mut stack, 120);
+        stack.push("c");
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a", "c"]);
+        assert!(!stack.go_forward());
+    }
+
+    #[test]
+    fn pop_during_go_forward() {
+        let mut frames = Frames::new();
+        let mut stack = NavStack::with_routes(vec!["a", "x", "b"]).forward_history(true);
+        show(&mut frames, &mut stack, 2);
+        stack.pop();
+        show(&mut frames, &mut stack, 120);
+
+        stack.go_forward();
+        show(&mut frames, &mut stack, 2);
+        stack.pop();
+        let actions = show(&mut frames, &mut stack, 120);
+
+        assert!(!actions.contains(&NavAction::Forwarded));
+        assert_eq!(stack.routes(), ["a"]);
+        assert!(!stack.is_transitioning());
+    }
+
+    #[test]
+    fn replace_during_go_forward() {
+        let mut frames = Frames::new();
+        let mut stack = NavStack::with_routes(vec!["a", "x", "b"]).forward_history(true);
+        show(&mut frames, &mut stack, 2);
+        stack.pop();
+        show(&mut frames, &mut stack, 120);
+
+        stack.go_forward();
+        show(&mut frames, &mut stack, 2);
+        stack.replace("c");
+        show(&mut frames, &mut stack, 120);
+
+        assert_eq!(stack.routes(), ["a", "c"]);
+        assert!(!stack.is_transitioning());
+    }
+
+    #[test]
+    fn replace_during_push_gets_a_new_entry() {
+        let mut frames = Frames::new();
+        let mut stack = NavStack::new("a");
+        let mut entries = std::collections::HashMap::new();
+        let mut run = |stack: &mut NavStack<&'static str>, n| {
+            frames.run(n, |ui| {
+                stack.show_mut(ui, |_ui, typ, nav| {
+                    if matches!(typ, NavUiType::Body) {
+                        entries.insert(*nav.top(), nav.entry_id());
+                    }
+                    RouteResponse {
+                        response: (),
+                        can_take_drag_from: vec![],
+                    }
+                });
+            });
+        };
+
+        stack.push("b");
+        run(&mut stack, 2);
+        stack.replace("c");
+        run(&mut stack, 120);
+
+        assert!(entries["c"].is_some());
+        assert_ne!(entries["b"], entries["c"]);
+    }
+
+    #[test]
+    fn guards_block_returns() {
+        let mut frames = Frames::new();
+        let mut stack = NavStack::with_routes(vec!["a", "b"]).return_guard(|route| {
+            if *route == "b" {
+                ReturnGuard::Block
+            } else {
+                ReturnGuard::Allow
+            }
+        });
+        show(&mut frames, &mut stack, 2);
+
+        stack.pop();
+        let actions = show(&mut frames, &mut stack, 120);
+        assert_eq!(
+            actions,
+            [NavAction::ReturnBlocked(
+                ReturnType::Click,
+                ReturnGuard::Block
+            )]
+        );
+        assert_eq!(stack.routes(), ["a", "b"]);
+        assert!(!stack.is_transitioning());
+    }
+
+    #[test]
+    fn confirmed_pops_skip_the_guard() {
+        let mut frames = Frames::new();
+        let mut stack =
+            NavStack::with_routes(vec!["a", "b", "c"]).return_guard(|_| ReturnGuard::Confirm);
+        show(&mut frames, &mut stack, 2);
+
+        stack.pop();
+        let actions = show(&mut frames, &mut stack, 120);
+        assert_eq!(
+            actions,
+            [NavAction::ReturnBlocked(
+                ReturnType::Click,
+                ReturnGuard::Confirm
+            )]
+        );
+
+        // asking again gets the same answer
+        stack.pop();
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a", "b", "c"]);
+
+        stack.pop_confirmed();
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a", "b"]);
+
+        // only that one pop was confirmed
+        stack.pop();
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a", "b"]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn persisted_routes_survive_a_restart() {
+        let id = egui::Id::new("stack");
+        let ctx = egui::Context::default();
+        let mut stack = NavStack::with_routes(vec!["a".to_owned(), "b".to_owned()]);
+        stack.forward.push("c".to_owned());
+        stack.persist(&ctx, id);
+        let saved = ctx.memory(ron::to_string).unwrap();
+
+        let ctx = egui::Context::default();
+        ctx.memory_mut(|m| *m = ron::from_str(&saved).unwrap());
+        let mut stack = NavStack::new("x".to_owned());
+        assert!(stack.restore(&ctx, id));
+        assert_eq!(stack.routes(), ["a", "b"]);
+        assert_eq!(stack.forward_route().map(String::as_str), Some("c"));
+
+        assert!(!NavStack::new("x".to_owned()).restore(&ctx, egui::Id::new("other")));
+    }
+
+    /// Show `outer`, with `inner` nested in its route "b"
+    fn show_nested(
+        ui: &mut egui::Ui,
+        outer: &mut NavStack<&'static str>,
+        inner: &mut NavStack<&'static str>,
+    ) {
+        outer.show_mut(ui, |ui, typ, nav| {
+            if matches!(typ, NavUiType::Body) && *nav.top() == "b" {
+                inner.show(ui, |_ui, _typ, _nav| RouteResponse {
+                    response: (),
+                    can_take_drag_from: vec![],
+                });
+            }
+            RouteResponse {
+                response: (),
+                can_take_drag_from: vec![],
+            }
+        });
+    }
+
+    #[test]
+    fn back_input_goes_to_the_innermost_nav() {
+        let mut frames = Frames::new();
+        let mut outer = NavStack::with_routes(vec!["a", "b"]).back_input(BackInput::Escape);
+        let mut inner = NavStack::with_routes(vec!["x", "y"])
+            .id_source(egui::Id::new("inner"))
+            .back_input(BackInput::Escape);
+        frames.run(2, |ui| show_nested(ui, &mut outer, &mut inner));
+
+        frames.press(egui::Key::Escape, |ui| {
+            show_nested(ui, &mut outer, &mut inner)
+        });
+        frames.run(120, |ui| show_nested(ui, &mut outer, &mut inner));
+        assert_eq!(inner.routes(), ["x"]);
+        assert_eq!(outer.routes(), ["a", "b"]);
+
+        // the inner nav is at its root now
+        frames.press(egui::Key::Escape, |ui| {
+            show_nested(ui, &mut outer, &mut inner)
+        });
+        frames.run(120, |ui| show_nested(ui, &mut outer, &mut inner));
+        assert_eq!(outer.routes(), ["a"]);
+    }
+
+    #[test]
+    fn returns_at_a_nested_root_go_to_the_enclosing_nav() {
+        let mut frames = Frames::new();
+        let mut outer = NavStack::with_routes(vec!["a", "b"]);
+        let inner_routes = ["x"];
+        let mut actions = vec![];
+        frames.run(120, |ui| {
+            outer.show_mut(ui, |ui, typ, nav| {
+                if matches!(typ, NavUiType::Body) && *nav.top() == "b" {
+                    let inner = Nav::new(&inner_routes)
+                        .id_source(egui::Id::new("inner"))
+                        .returning(true);
+                    actions.extend(
+                        inner
+                            .show(ui, |_ui, _typ, _nav| RouteResponse {
+                                response: (),
+                                can_take_drag_from: vec![],
+                            })
+                            .action,
+                    );
+                }
+                RouteResponse {
+                    response: (),
+                    can_take_drag_from: vec![],
+                }
+            });
+        });
+
+        assert_eq!(outer.routes(), ["a"]);
+        assert!(actions.contains(&NavAction::ReturnDelegated));
+    }
+
+    #[test]
+    fn replace_during_pop() {
+        let mut frames = Frames::new();
+        let mut stack = NavStack::with_routes(vec!["a", "b"]);
+        show(&mut frames, &mut stack, 2);
+
+        stack.pop();
+        show(&mut frames, &mut stack, 2);
+        stack.replace("c");
+        show(&mut frames, &mut stack, 120);
+
+        assert_eq!(stack.routes(), ["a", "c"]);
+        assert!(!stack.is_transitioning());
+    }
+
+    #[test]
+    fn pop_during_replace() {
+        let mut frames = Frames::new();
+        let mut stack = NavStack::with_routes(vec!["a", "b"]);
+        show(&mut frames, &mut stack, 2);
+
+        stack.replace("c");
+        show(&mut frames, &mut stack, 2);
+        stack.pop();
+        show(&mut frames, &mut stack, 120);
+
+        assert_eq!(stack.routes(), ["a"]);
+        assert!(!stack.is_transitioning());
+    }
+
+    #[test]
+    fn history_follows_the_stack() {
+        let mut frames = Frames::new();
+        let history = MemoryHistory::new("a");
+        let mut stack = NavStack::new("a")
+            .forward_history(true)
+            .history(history.clone());
+        show(&mut frames, &mut stack, 2);
+
+        // pushes are reported right away
+        stack.push("b");
+        assert_eq!(history.routes(), ["a", "b"]);
+        show(&mut frames, &mut stack, 120);
+
+        // pops once the transition is done
+        stack.pop();
+        assert_eq!(history.routes(), ["a", "b"]);
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(history.routes(), ["a"]);
+        assert_eq!(history.forward_routes(), ["b"]);
+
+        stack.go_forward();
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a", "b"]);
+        assert_eq!(history.routes(), ["a", "b"]);
+
+        stack.replace("c");
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a", "c"]);
+        assert_eq!(history.routes(), ["a", "c"]);
+    }
+
+    #[test]
+    fn history_follows_navigate_to() {
+        let mut frames = Frames::new();
+        let history = MemoryHistory::new("a");
+        let mut stack = NavStack::new("a").history(history.clone());
+        show(&mut frames, &mut stack, 2);
+
+        stack.navigate_to(vec!["a", "b", "c"]);
+        assert_eq!(history.routes(), ["a", "b", "c"]);
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a", "b", "c"]);
+
+        stack.navigate_to(vec!["a", "d"]);
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a", "d"]);
+        assert_eq!(history.routes(), ["a", "d"]);
+
+        stack.navigate_to(vec!["a"]);
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a"]);
+        assert_eq!(history.routes(), ["a"]);
+    }
+
+    #[test]
+    fn refused_history_backs_are_undone() {
+        let mut frames = Frames::new();
+        let history = MemoryHistory::new("a");
+        let mut stack = NavStack::new("a")
+            .return_guard(|route| {
+                if *route == "b" {
+                    ReturnGuard::Confirm
+                } else {
+                    ReturnGuard::Allow
+                }
+            })
+            .history(history.clone());
+        stack.push("b");
+        show(&mut frames, &mut stack, 120);
+
+        history.back();
+        assert_eq!(history.routes(), ["a"]);
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a", "b"]);
+        assert_eq!(history.routes(), ["a", "b"]);
+
+        // the user confirmed
+        stack.pop_confirmed();
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a"]);
+        assert_eq!(history.routes(), ["a"]);
+        assert_eq!(history.forward_routes(), ["b"]);
+    }
+
+    #[test]
+    fn stack_follows_history_events() {
+        let mut frames = Frames::new();
+        let history = MemoryHistory::new("a");
+        let mut stack = NavStack::new("a")
+            .forward_history(true)
+            .history(history.clone());
+        stack.push("b");
+        show(&mut frames, &mut stack, 120);
+
+        history.back();
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a"]);
+        assert_eq!(history.routes(), ["a"]);
+
+        history.go_forward();
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a", "b"]);
+        assert_eq!(history.routes(), ["a", "b"]);
+    }
+
+    #[test]
+    fn replace_during_push() {
+        let mut frames = Frames::new();
+        let mut stack = NavStack::new("a");
+        show(&mut frames, &mut stack, 2);
+
+        stack.push("b");
+        show(&mut frames, &mut stack, 2);
+        stack.replace("c");
+        assert_eq!(stack.routes(), ["a", "c"]);
+
+        // the push carries on with the new route, no second transition
+        show(&mut frames, &mut stack, 120);
+        assert_eq!(stack.routes(), ["a", "c"]);
+        assert!(!stack.is_transitioning());
+    }
+}
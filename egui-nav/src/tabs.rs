@@ -0,0 +1,282 @@
+use egui::{vec2, LayerId, Order};
+
+use crate::{
+    render_bg, render_fg, Animation, CrossFadeTransition, LayerTransition, LayoutDirection, Nav,
+    NavResponse, NavStack, NavTransition, NavUiType, RouteResponse, TransitionContext,
+};
+
+/// A [`NavStack`] per tab, showing only the active one. Each tab keeps
+/// its own transitions and widget state, like scroll positions, while
+/// the other tabs are shown.
+pub struct NavTabs<Route: Clone> {
+    tabs: Vec<NavStack<Route>>,
+    active: usize,
+    id_source: Option<egui::Id>,
+    animation: Animation,
+    transition: Box<dyn NavTransition>,
+    /// the tab we're switching away from, and for how many seconds
+    switching: Option<(usize, f32)>,
+}
+
+impl<Route: Clone> NavTabs<Route> {
+    pub fn new(tabs: Vec<NavStack<Route>>) -> Self {
+        // we always have an active tab
+        assert!(!tabs.is_empty(), "NavTabs needs at least one tab");
+
+        NavTabs {
+            tabs,
+            active: 0,
+            id_source: None,
+            animation: Animation::default(),
+            transition: Box::new(CrossFadeTransition),
+            switching: None,
+        }
+    }
+
+    pub fn id_source(mut self, id: egui::Id) -> Self {
+        self.id_source = Some(id);
+        self
+    }
+
+    /// How long switching tabs takes
+    pub fn animation(mut self, animation: Animation) -> Self {
+        self.animation = animation;
+        self
+    }
+
+    /// How tabs are drawn while switching. Defaults to
+    /// [`CrossFadeTransition`], [`crate::SlideTransition`] slides in
+    /// the order of the tabs.
+    pub fn transition(mut self, transition: impl NavTransition + 'static) -> Self {
+        self.transition = Box::new(transition);
+        self
+    }
+
+    pub fn tabs(&self) -> &[NavStack<Route>] {
+        &self.tabs
+    }
+
+    pub fn tab_mut(&mut self, index: usize) -> Option<&mut NavStack<Route>> {
+        self.tabs.get_mut(index)
+    }
+
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    pub fn active_tab(&self) -> &NavStack<Route> {
+        &self.tabs[self.active]
+    }
+
+    pub fn active_tab_mut(&mut self) -> &mut NavStack<Route> {
+        &mut self.tabs[self.active]
+    }
+
+    /// Are we animating from one tab to another?
+    pub fn is_switching(&self) -> bool {
+        self.switching.is_some()
+    }
+
+    /// Switch to the tab at `index`. Selecting the active tab again pops
+    /// it back to its root.
+    pub fn select(&mut self, index: usize) {
+        if index >= self.tabs.len() {
+            return;
+        }
+
+        if index == self.active {
+            self.tabs[index].pop_to_root();
+            return;
+        }
+
+        self.switching = Some((self.active, 0.0));
+        self.active = index;
+    }
+
+    pub fn show<F, R>(&mut self, ui: &mut egui::Ui, show_route: F) -> NavResponse<R>
+    where
+        F: Fn(&mut egui::Ui, NavUiType, &Nav<Route>) -> RouteResponse<R>,
+    {
+        let mut show_route = show_route;
+
+        self.show_internal(ui, &mut show_route)
+    }
+
+    pub fn show_mut<F, R>(&mut self, ui: &mut egui::Ui, mut show_route: F) -> NavResponse<R>
+    where
+        F: FnMut(&mut egui::Ui, NavUiType, &Nav<Route>) -> RouteResponse<R>,
+    {
+        self.show_internal(ui, &mut show_route)
+    }
+
+    fn show_internal<F, R>(&mut self, ui: &mut egui::Ui, show_route: &mut F) -> NavResponse<R>
+    where
+        F: FnMut(&mut egui::Ui, NavUiType, &Nav<Route>) -> RouteResponse<R>,
+    {
+        let id = ui.id().with(("nav-tabs", self.id_source));
+        let rect = ui.available_rect_before_wrap();
+
+        let mut progress = 1.0;
+        if let Some((_, elapsed)) = &mut self.switching {
+            *elapsed += ui.input(|i| i.stable_dt).min(0.1);
+            if self.transition.animated() && *elapsed < self.animation.duration {
+                progress = (self.animation.easing)(*elapsed / self.animation.duration);
+                ui.ctx().request_repaint();
+            } else {
+                self.switching = None;
+            }
+        }
+
+        // every tab renders in its own id scope, so nothing is shared
+        // between their navs
+        let active_id = id.with(("tab", self.active));
+
+        let Some((from, _)) = self.switching else {
+            let active = &mut self.tabs[self.active];
+            let layer_id = ui.layer_id();
+            return render_fg(
+                ui,
+                active_id,
+                layer_id,
+                LayerTransition::IDENTITY,
+                rect,
+                rect,
+                |ui| RouteResponse {
+                    response: active.show_mut(ui, &mut *show_route),
+                    can_take_drag_from: Vec::new(),
+                },
+            )
+            .response;
+        };
+
+        // tabs further along come in from the trailing side
+        let sign = LayoutDirection::from_ui(ui).sign();
+        let direction = if self.active > from {
+            vec2(sign, 0.0)
+        } else {
+            vec2(-sign, 0.0)
+        };
+        let fg_layer = LayerId::new(Order::Foreground, id.with("fg"));
+        let layout = self.transition.layout(&TransitionContext {
+            progress,
+            direction,
+            fg_rect: rect,
+            bg_rect: rect,
+            fg_layer,
+            bg_layer: LayerId::new(Order::Background, ui.id()),
+        });
+
+        let previous = &mut self.tabs[from];
+        render_bg(
+            ui,
+            id.with(("tab", from)),
+            layout.bg,
+            layout.bg.clip_rect(rect),
+            rect,
+            |ui| {
+                previous.show_mut(ui, &mut *show_route);
+                Vec::new()
+            },
+        );
+
+        let active = &mut self.tabs[self.active];
+        render_fg(
+            ui,
+            active_id,
+            fg_layer,
+            layout.fg,
+            layout.fg.clip_rect(rect),
+            rect,
+            |ui| RouteResponse {
+                response: active.show_mut(ui, &mut *show_route),
+                can_take_drag_from: Vec::new(),
+            },
+        )
+        .response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Frames;
+
+    fn show(frames: &mut Frames, tabs: &mut NavTabs<&'static str>, n: usize) {
+        frames.run(n, |ui| {
+            tabs.show(ui, |_ui, _typ, _nav| RouteResponse {
+                response: (),
+                can_take_drag_from: vec![],
+            });
+        });
+    }
+
+    #[test]
+    fn switching_tabs_keeps_their_stacks() {
+        let mut frames = Frames::new();
+        let mut tabs = NavTabs::new(vec![
+            NavStack::with_routes(vec!["a", "b"]),
+            NavStack::with_routes(vec!["c"]),
+        ]);
+        show(&mut frames, &mut tabs, 2);
+
+        tabs.select(1);
+        assert!(tabs.is_switching());
+        tabs.active_tab_mut().push("d");
+        show(&mut frames, &mut tabs, 120);
+        assert!(!tabs.is_switching());
+        assert_eq!(tabs.active(), 1);
+        assert_eq!(tabs.active_tab().routes(), ["c", "d"]);
+
+        tabs.select(0);
+        show(&mut frames, &mut tabs, 120);
+        assert_eq!(tabs.active_tab().routes(), ["a", "b"]);
+        assert_eq!(tabs.tabs()[1].routes(), ["c", "d"]);
+    }
+
+    #[test]
+    fn selecting_the_active_tab_pops_it_to_its_root() {
+        let mut frames = Frames::new();
+        let mut tabs = NavTabs::new(vec![
+            NavStack::with_routes(vec!["a", "b", "c"]),
+            NavStack::with_routes(vec!["d", "e"]),
+        ]);
+        show(&mut frames, &mut tabs, 2);
+
+        tabs.select(0);
+        assert!(!tabs.is_switching());
+        show(&mut frames, &mut tabs, 120);
+        assert_eq!(tabs.active_tab().routes(), ["a"]);
+        assert_eq!(tabs.tabs()[1].routes(), ["d", "e"]);
+    }
+
+    #[test]
+    fn tabs_do_not_share_widget_state() {
+        let mut frames = Frames::new();
+        let mut tabs = NavTabs::new(vec![
+            NavStack::with_routes(vec!["a"]),
+            NavStack::with_routes(vec!["a"]),
+        ]);
+        let mut ids = vec![];
+        let mut show_ids = |frames: &mut Frames, tabs: &mut NavTabs<&'static str>| {
+            frames.run(1, |ui| {
+                tabs.show_mut(ui, |ui, typ, _nav| {
+                    if matches!(typ, NavUiType::Body) {
+                        ids.push(ui.id());
+                    }
+                    RouteResponse {
+                        response: (),
+                        can_take_drag_from: vec![],
+                    }
+                });
+            });
+        };
+
+        show_ids(&mut frames, &mut tabs);
+        tabs.select(1);
+        show(&mut frames, &mut tabs, 120);
+        show_ids(&mut frames, &mut tabs);
+
+        assert_eq!(ids.len(), 2);
+        assert_ne!(ids[0], ids[1]);
+    }
+}
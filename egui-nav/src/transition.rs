@@ -0,0 +1,329 @@
+use crate::util;
+use egui::{emath::TSTransform, vec2, Color32, LayerId, Rect, Vec2};
+
+/// Everything a [`NavTransition`] gets to lay out one frame of a transition
+#[derive(Clone, Copy, Debug)]
+pub struct TransitionContext {
+    /// How much of the foreground route is presented. 0.0 means the
+    /// foreground is fully gone, 1.0 means it is fully shown.
+    pub progress: f32,
+
+    /// Unit vector pointing to where the foreground goes when it leaves.
+    /// This is mirrored in right to left layouts and points down for
+    /// vertical navs.
+    pub direction: Vec2,
+
+    /// The rect the foreground route is rendered in
+    pub fg_rect: Rect,
+
+    /// The rect the background route occupied last frame
+    pub bg_rect: Rect,
+
+    pub fg_layer: LayerId,
+    pub bg_layer: LayerId,
+}
+
+impl TransitionContext {
+    /// The size of `rect` along the direction of the transition
+    pub fn extent(&self, rect: Rect) -> f32 {
+        (rect.size() * self.direction).length()
+    }
+}
+
+/// How a single route layer is drawn for one frame of a transition
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayerTransition {
+    pub translation: Vec2,
+
+    /// Scale around the center of the layer's rect
+    pub scale: f32,
+
+    /// Opacity of the route contents, 0.0 to 1.0
+    pub opacity: f32,
+
+    /// Color painted over the route after it has been rendered
+    pub scrim: Color32,
+
+    /// The part of the screen this layer may draw into. None means the
+    /// whole rect of the nav.
+    pub clip: Option<Rect>,
+}
+
+impl LayerTransition {
+    pub const IDENTITY: Self = Self {
+        translation: Vec2::ZERO,
+        scale: 1.0,
+        opacity: 1.0,
+        scrim: Color32::TRANSPARENT,
+        clip: None,
+    };
+
+    /// The transform for a layer rendered in `rect`
+    pub fn transform(&self, rect: Rect) -> TSTransform {
+        // scale around the center of the rect
+        let center = rect.center().to_vec2();
+        TSTransform::new(self.translation + center * (1.0 - self.scale), self.scale)
+    }
+
+    /// The clip rect, in untransformed coordinates, for a layer rendered
+    /// in `rect`
+    pub(crate) fn clip_rect(&self, rect: Rect) -> Rect {
+        let visible = self.clip.map_or(rect, |clip| clip.intersect(rect));
+        self.transform(rect).inverse() * visible
+    }
+}
+
+impl Default for LayerTransition {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TransitionLayout {
+    pub fg: LayerTransition,
+    pub bg: LayerTransition,
+}
+
+/// Decides how the foreground and background routes of a [`crate::Nav`]
+/// are drawn while transitioning between them.
+pub trait NavTransition {
+    fn layout(&self, ctx: &TransitionContext) -> TransitionLayout;
+
+    /// Transitions that return false here finish immediately
+    fn animated(&self) -> bool {
+        true
+    }
+}
+
+/// iOS style push. The foreground slides in from the side while the
+/// background moves a bit slower and is darkened.
+#[derive(Clone, Copy, Debug)]
+pub struct PushTransition {
+    /// How far the background is shifted when fully covered, as a
+    /// fraction of its width
+    pub parallax: f32,
+
+    /// Darkening of the background when fully covered (max is 255)
+    pub scrim_alpha: u8,
+}
+
+impl PushTransition {
+    pub const fn new() -> Self {
+        Self {
+            parallax: 0.1,
+            scrim_alpha: 50,
+        }
+    }
+}
+
+impl Default for PushTransition {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NavTransition for PushTransition {
+    fn layout(&self, ctx: &TransitionContext) -> TransitionLayout {
+        let extent = ctx.extent(ctx.fg_rect);
+        let offset = (1.0 - ctx.progress) * extent;
+        let bg_shift = (-ctx.extent(ctx.bg_rect) * self.parallax + offset * 0.3).min(0.0);
+
+        // the background is only visible in the strip the foreground
+        // has uncovered
+        let bg_clip = util::edge_strip(ctx.fg_rect, ctx.direction, offset);
+
+        TransitionLayout {
+            fg: LayerTransition {
+                translation: ctx.direction * offset,
+                ..LayerTransition::IDENTITY
+            },
+            bg: LayerTransition {
+                translation: ctx.direction * bg_shift,
+                scrim: scrim(self.scrim_alpha, ctx.progress),
+                clip: Some(bg_clip),
+                ..LayerTransition::IDENTITY
+            },
+        }
+    }
+}
+
+/// Material style fade through. The background fades out, then the
+/// foreground fades in while scaling up slightly.
+#[derive(Clone, Copy, Debug)]
+pub struct FadeThroughTransition {
+    /// Scale of the foreground when it starts fading in
+    pub start_scale: f32,
+}
+
+impl Default for FadeThroughTransition {
+    fn default() -> Self {
+        Self { start_scale: 0.92 }
+    }
+}
+
+impl NavTransition for FadeThroughTransition {
+    fn layout(&self, ctx: &TransitionContext) -> TransitionLayout {
+        let bg_opacity = (1.0 - ctx.progress * 2.0).clamp(0.0, 1.0);
+        let fg_opacity = (ctx.progress * 2.0 - 1.0).clamp(0.0, 1.0);
+
+        TransitionLayout {
+            fg: LayerTransition {
+                scale: egui::lerp(self.start_scale..=1.0, fg_opacity),
+                opacity: fg_opacity,
+                ..LayerTransition::IDENTITY
+            },
+            bg: LayerTransition {
+                opacity: bg_opacity,
+                ..LayerTransition::IDENTITY
+            },
+        }
+    }
+}
+
+/// The foreground covers the background by sliding up from the bottom.
+#[derive(Clone, Copy, Debug)]
+pub struct CoverTransition {
+    /// Darkening of the background when fully covered (max is 255)
+    pub scrim_alpha: u8,
+}
+
+impl Default for CoverTransition {
+    fn default() -> Self {
+        Self { scrim_alpha: 120 }
+    }
+}
+
+impl NavTransition for CoverTransition {
+    fn layout(&self, ctx: &TransitionContext) -> TransitionLayout {
+        let offset = (1.0 - ctx.progress) * ctx.fg_rect.height();
+        let bg_clip = Rect::from_min_size(ctx.fg_rect.min, vec2(ctx.fg_rect.width(), offset));
+
+        TransitionLayout {
+            fg: LayerTransition {
+                translation: vec2(0.0, offset),
+                ..LayerTransition::IDENTITY
+            },
+            bg: LayerTransition {
+                scrim: scrim(self.scrim_alpha, ctx.progress),
+                clip: Some(bg_clip),
+                ..LayerTransition::IDENTITY
+            },
+        }
+    }
+}
+
+/// The foreground zooms and fades in on top of the background, which
+/// grows slightly and fades out.
+#[derive(Clone, Copy, Debug)]
+pub struct ZoomTransition {
+    /// Scale of the foreground when it is fully gone
+    pub fg_start_scale: f32,
+
+    /// Scale of the background when it is fully covered
+    pub bg_end_scale: f32,
+}
+
+impl Default for ZoomTransition {
+    fn default() -> Self {
+        Self {
+            fg_start_scale: 0.85,
+            bg_end_scale: 1.05,
+        }
+    }
+}
+
+impl NavTransition for ZoomTransition {
+    fn layout(&self, ctx: &TransitionContext) -> TransitionLayout {
+        let p = ctx.progress.clamp(0.0, 1.0);
+
+        TransitionLayout {
+            fg: LayerTransition {
+                scale: egui::lerp(self.fg_start_scale..=1.0, p),
+                opacity: p,
+                ..LayerTransition::IDENTITY
+            },
+            bg: LayerTransition {
+                scale: egui::lerp(1.0..=self.bg_end_scale, p),
+                opacity: 1.0 - p,
+                ..LayerTransition::IDENTITY
+            },
+        }
+    }
+}
+
+/// The foreground fades in over the background, which fades out
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CrossFadeTransition;
+
+impl NavTransition for CrossFadeTransition {
+    fn layout(&self, ctx: &TransitionContext) -> TransitionLayout {
+        let progress = ctx.progress.clamp(0.0, 1.0);
+
+        TransitionLayout {
+            fg: LayerTransition {
+                opacity: progress,
+                ..LayerTransition::IDENTITY
+            },
+            bg: LayerTransition {
+                opacity: 1.0 - progress,
+                ..LayerTransition::IDENTITY
+            },
+        }
+    }
+}
+
+/// Both routes slide together, the foreground pushing the background
+/// out of the way. Good for switching between tabs.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SlideTransition;
+
+impl NavTransition for SlideTransition {
+    fn layout(&self, ctx: &TransitionContext) -> TransitionLayout {
+        let extent = ctx.extent(ctx.fg_rect);
+        let offset = (1.0 - ctx.progress) * extent;
+
+        TransitionLayout {
+            fg: LayerTransition {
+                translation: ctx.direction * offset,
+                ..LayerTransition::IDENTITY
+            },
+            bg: LayerTransition {
+                translation: ctx.direction * (offset - extent),
+                ..LayerTransition::IDENTITY
+            },
+        }
+    }
+}
+
+/// No animation, routes are swapped immediately
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InstantTransition;
+
+impl NavTransition for InstantTransition {
+    fn layout(&self, ctx: &TransitionContext) -> TransitionLayout {
+        // only reached while dragging, where we still want the route
+        // behind to show up once the foreground is mostly gone
+        let fg_shown = ctx.progress > 0.5;
+
+        TransitionLayout {
+            fg: LayerTransition {
+                opacity: if fg_shown { 1.0 } else { 0.0 },
+                ..LayerTransition::IDENTITY
+            },
+            bg: LayerTransition {
+                opacity: if fg_shown { 0.0 } else { 1.0 },
+                ..LayerTransition::IDENTITY
+            },
+        }
+    }
+
+    fn animated(&self) -> bool {
+        false
+    }
+}
+
+fn scrim(max_alpha: u8, progress: f32) -> Color32 {
+    let alpha = (progress.clamp(0.0, 1.0) * max_alpha as f32) as u8;
+    Color32::from_black_alpha(alpha)
+}
@@ -1,6 +1,72 @@
+use crate::DragDirection;
+
 /// What part of the nav are we rendering? We opt to using a single
 /// callback rendering to avoid borrow issues
 pub enum NavUiType {
     Title,
     Body,
 }
+
+/// Which way the navigation reads. New routes come in from the trailing
+/// edge and swiping towards it goes back.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LayoutDirection {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl LayoutDirection {
+    /// Detect the direction from the ui's layout
+    pub fn from_ui(ui: &egui::Ui) -> Self {
+        if ui.layout().prefer_right_to_left() {
+            LayoutDirection::RightToLeft
+        } else {
+            LayoutDirection::LeftToRight
+        }
+    }
+
+    /// The sign of x offsets that move towards the trailing edge
+    pub(crate) fn sign(self) -> f32 {
+        match self {
+            LayoutDirection::LeftToRight => 1.0,
+            LayoutDirection::RightToLeft => -1.0,
+        }
+    }
+
+    /// The drag that moves towards the trailing edge
+    pub(crate) fn drag_direction(self) -> DragDirection {
+        match self {
+            LayoutDirection::LeftToRight => DragDirection::LeftToRight,
+            LayoutDirection::RightToLeft => DragDirection::RightToLeft,
+        }
+    }
+
+    /// The drag that moves towards the leading edge
+    pub(crate) fn reverse_drag_direction(self) -> DragDirection {
+        match self {
+            LayoutDirection::LeftToRight => DragDirection::RightToLeft,
+            LayoutDirection::RightToLeft => DragDirection::LeftToRight,
+        }
+    }
+}
+
+/// The axis routes slide along
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Axis {
+    /// Routes come in from the trailing edge and swipe back sideways
+    #[default]
+    Horizontal,
+
+    /// Routes slide up from the bottom and swipe back down
+    Vertical,
+}
+
+impl Axis {
+    /// The size of `rect` along this axis
+    pub(crate) fn extent(self, rect: egui::Rect) -> f32 {
+        match self {
+            Axis::Horizontal => rect.width(),
+            Axis::Vertical => rect.height(),
+        }
+    }
+}
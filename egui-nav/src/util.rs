@@ -1,3 +1,20 @@
+/// The strip of `rect`, `size` thick, along the edge opposite to `dir`.
+/// That's the edge something moving in `dir` uncovers first.
+pub(crate) fn edge_strip(rect: egui::Rect, dir: egui::Vec2, size: f32) -> egui::Rect {
+    let mut strip = rect;
+    if dir.x > 0.0 {
+        strip.max.x = rect.min.x + size.min(rect.width());
+    } else if dir.x < 0.0 {
+        strip.min.x = rect.max.x - size.min(rect.width());
+    }
+    if dir.y > 0.0 {
+        strip.max.y = rect.min.y + size.min(rect.height());
+    } else if dir.y < 0.0 {
+        strip.min.y = rect.max.y - size.min(rect.height());
+    }
+    strip
+}
+
 pub fn arr_top_n<T>(ts: &[T], n: usize) -> Option<&T> {
     let ind = ts.len() as i32 - (n as i32) - 1;
     if ind < 0 {
@@ -2,12 +2,16 @@ use eframe::egui;
 use egui::Frame;
 use egui_demo_lib::{easy_mark::EasyMarkEditor, ColorTest};
 use egui_nav::{
-    DefaultNavTitle, DefaultTitleResponse, Nav, NavAction, NavUiType, PopupSheet, RouteResponse,
+    BackInput, DefaultNavTitle, DefaultTitleResponse, NavAction, NavStack, NavUiType, PopupSheet,
+    RouteResponse,
 };
 use std::fmt;
 
-fn test_routes() -> Vec<Route> {
-    vec![Route::Editor, Route::ColorTest, Route::Editor]
+fn test_routes() -> NavStack<Route> {
+    NavStack::with_routes(vec![Route::Editor, Route::ColorTest, Route::Editor])
+        .forward_history(true)
+        .back_input(BackInput::AltLeft | BackInput::MouseBack)
+        .route_label(|route: &Route| route.to_string())
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -24,7 +28,7 @@ fn main() -> Result<(), eframe::Error> {
             Ok(Box::new(MyApp {
                 navigating: false,
                 returning: false,
-                routes: test_routes(),
+                stack: test_routes(),
                 popup: None,
             }))
         }),
@@ -46,7 +50,10 @@ fn main() {
                 web_options,
                 Box::new(|cc| {
                     Box::new(MyApp {
-                        routes: test_routes(),
+                        stack: test_routes(),
+                        popup: None,
+                        navigating: false,
+                        returning: false,
                     })
                 }),
             )
@@ -55,10 +62,10 @@ fn main() {
     });
 }
 
-#[derive(Default)]
 struct MyApp {
-    routes: Vec<Route>,
+    stack: NavStack<Route>,
     popup: Option<Route>,
+    // popup sheet transition flags, the nav stack tracks its own
     navigating: bool,
     returning: bool,
 }
@@ -88,137 +95,134 @@ fn nav_ui(ui: &mut egui::Ui, app: &mut MyApp) {
     ui.visuals_mut().interact_cursor = Some(egui::CursorIcon::PointingHand);
 
     if let Some(popup) = app.popup {
-        if let Some(bg_route) = app.routes.last() {
-            let resp = PopupSheet::new(bg_route, &popup)
-                .navigating(app.navigating)
-                .returning(app.returning)
-                .show(ui, |ui, typ, bg_route| match typ {
-                    NavUiType::Title => {
-                        DefaultNavTitle::default()
-                            .ui(ui, &[&bg_route])
-                            .map(|n| match n {
-                                DefaultTitleResponse::Back => OurNavAction::Returning,
-                            })
-                    }
+        let bg_route = app.stack.top();
+        let resp = PopupSheet::new(bg_route, &popup)
+            .navigating(app.navigating)
+            .returning(app.returning)
+            .back_input(BackInput::Escape)
+            .show(ui, |ui, typ, bg_route| match typ {
+                NavUiType::Title => {
+                    DefaultNavTitle::default()
+                        .ui(ui, &[&bg_route])
+                        .map(|n| match n {
+                            DefaultTitleResponse::Back => OurNavAction::Returning,
+                        })
+                }
 
-                    NavUiType::Body => match *bg_route {
-                        Route::Editor => {
-                            ui.vertical(|ui| {
-                                let mut action: Option<OurNavAction> = None;
-
-                                if ui.button("Color Test").clicked() {
-                                    action = Some(OurNavAction::Navigating(Route::ColorTest));
-                                }
-
-                                if ui.button("Popup color test").clicked() {
-                                    action = Some(OurNavAction::Popup(Route::ColorTest));
-                                }
-
-                                let _ = ui.button("Back");
-
-                                EasyMarkEditor::default().ui(ui);
-                                action
-                            })
-                            .inner
-                        }
-
-                        Route::ColorTest => {
-                            ui.vertical(|ui| {
-                                let mut action: Option<OurNavAction> = None;
-                                if ui.button("Editor").clicked() {
-                                    action = Some(OurNavAction::Navigating(Route::Editor));
-                                }
-                                let _ = ui.button("Back");
-                                ColorTest::default().ui(ui);
-                                action
-                            })
-                            .inner
-                        }
-                    },
-                });
+                NavUiType::Body => match *bg_route {
+                    Route::Editor => {
+                        ui.vertical(|ui| {
+                            let mut action: Option<OurNavAction> = None;
 
-            if let Some(NavAction::Returned(_)) = resp.action {
-                app.popup = None;
-                app.returning = false;
-            } else if let Some(NavAction::Navigated) = resp.action {
-                app.navigating = false;
-            }
+                            if ui.button("Color Test").clicked() {
+                                action = Some(OurNavAction::Navigating(Route::ColorTest));
+                            }
+
+                            if ui.button("Popup color test").clicked() {
+                                action = Some(OurNavAction::Popup(Route::ColorTest));
+                            }
 
-            return;
+                            let _ = ui.button("Back");
+
+                            EasyMarkEditor::default().ui(ui);
+                            action
+                        })
+                        .inner
+                    }
+
+                    Route::ColorTest => {
+                        ui.vertical(|ui| {
+                            let mut action: Option<OurNavAction> = None;
+                            if ui.button("Editor").clicked() {
+                                action = Some(OurNavAction::Navigating(Route::Editor));
+                            }
+                            let _ = ui.button("Back");
+                            ColorTest::default().ui(ui);
+                            action
+                        })
+                        .inner
+                    }
+                },
+            });
+
+        if let Some(NavAction::Returned(..)) = resp.action {
+            app.popup = None;
+            app.returning = false;
+            app.navigating = false;
+        } else if let Some(NavAction::Navigated) = resp.action {
+            app.navigating = false;
         }
+
+        return;
     }
 
-    let response = Nav::new(&app.routes)
-        .navigating(app.navigating)
-        .returning(app.returning)
-        .show(ui, |ui, typ, nav| match typ {
-            NavUiType::Title => {
-                let r = DefaultNavTitle::default()
-                    .ui(ui, nav.routes())
-                    .map(|n| match n {
-                        DefaultTitleResponse::Back => OurNavAction::Returning,
-                    });
-
-                RouteResponse {
-                    response: r,
-                    can_take_drag_from: Vec::new(),
-                }
+    let response = app.stack.show(ui, |ui, typ, nav| match typ {
+        NavUiType::Title => {
+            let r = DefaultNavTitle::default()
+                .ui(ui, nav.routes())
+                .map(|n| match n {
+                    DefaultTitleResponse::Back => OurNavAction::Returning,
+                });
+
+            RouteResponse {
+                response: r,
+                can_take_drag_from: Vec::new(),
             }
+        }
 
-            NavUiType::Body => match nav.top() {
-                Route::Editor => {
-                    ui.vertical(|ui| {
-                        let mut action: Option<OurNavAction> = None;
+        NavUiType::Body => match nav.top() {
+            Route::Editor => {
+                ui.vertical(|ui| {
+                    let mut action: Option<OurNavAction> = None;
 
-                        if ui.button("Color Test").clicked() {
-                            action = Some(OurNavAction::Navigating(Route::ColorTest));
-                        }
+                    if ui.button("Color Test").clicked() {
+                        action = Some(OurNavAction::Navigating(Route::ColorTest));
+                    }
 
-                        if ui.button("Popup color test").clicked() {
-                            action = Some(OurNavAction::Popup(Route::ColorTest));
-                        }
+                    if ui.button("Popup color test").clicked() {
+                        action = Some(OurNavAction::Popup(Route::ColorTest));
+                    }
 
-                        if nav.routes().len() > 1 && ui.button("Back").clicked() {
-                            action = Some(OurNavAction::Returning);
-                        }
+                    if nav.routes().len() > 1 && ui.button("Back").clicked() {
+                        action = Some(OurNavAction::Returning);
+                    }
 
-                        EasyMarkEditor::default().ui(ui);
-                        RouteResponse {
-                            response: action,
-                            can_take_drag_from: Vec::new(),
-                        }
-                    })
-                    .inner
-                }
+                    EasyMarkEditor::default().ui(ui);
+                    RouteResponse {
+                        response: action,
+                        can_take_drag_from: Vec::new(),
+                    }
+                })
+                .inner
+            }
 
-                Route::ColorTest => {
-                    ui.vertical(|ui| {
-                        let mut action: Option<OurNavAction> = None;
-                        if ui.button("Editor").clicked() {
-                            action = Some(OurNavAction::Navigating(Route::Editor));
-                        }
-                        if nav.routes().len() > 1 && ui.button("Back").clicked() {
-                            action = Some(OurNavAction::Returning);
-                        }
-                        ColorTest::default().ui(ui);
-                        RouteResponse {
-                            response: action,
-                            can_take_drag_from: Vec::new(),
-                        }
-                    })
-                    .inner
-                }
-            },
-        });
+            Route::ColorTest => {
+                ui.vertical(|ui| {
+                    let mut action: Option<OurNavAction> = None;
+                    if ui.button("Editor").clicked() {
+                        action = Some(OurNavAction::Navigating(Route::Editor));
+                    }
+                    if nav.routes().len() > 1 && ui.button("Back").clicked() {
+                        action = Some(OurNavAction::Returning);
+                    }
+                    ColorTest::default().ui(ui);
+                    RouteResponse {
+                        response: action,
+                        can_take_drag_from: Vec::new(),
+                    }
+                })
+                .inner
+            }
+        },
+    });
 
     if let Some(action) = response.response.or(response.title_response) {
         match action {
             OurNavAction::Navigating(route) => {
-                app.navigating = true;
-                app.routes.push(route);
+                app.stack.push(route);
             }
             OurNavAction::Returning => {
-                app.returning = true;
+                app.stack.pop();
             }
             OurNavAction::Popup(route) => {
                 app.popup = Some(route);
@@ -227,14 +231,8 @@ fn nav_ui(ui: &mut egui::Ui, app: &mut MyApp) {
         }
     }
 
-    if let Some(action) = response.action {
-        if let NavAction::Returned(_) = action {
-            app.routes.pop();
-            app.returning = false;
-            println!("Popped route {:?}", app.routes);
-        } else if let NavAction::Navigated = action {
-            app.navigating = false;
-        }
+    if let Some(NavAction::Returned(..)) = response.action {
+        println!("Popped route {:?}", app.stack.routes());
     }
 }
 